@@ -1,39 +1,29 @@
-use crate::content;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Active tab in the portfolio.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Tab {
-    About,
-    Projects,
-    Skills,
-    Contact,
-}
-
-impl Tab {
-    pub const ALL: [Tab; 4] = [Tab::About, Tab::Projects, Tab::Skills, Tab::Contact];
+use crate::caps::Capabilities;
+use crate::clipboard;
+use crate::command::{self, Command, CommandState};
+use crate::keymap::{Action, HelpSection, Keymap};
+use crate::markdown;
+use crate::portfolio::{self, ContactEntry, Portfolio, ProjectCategory, Role, Tab};
+use crate::posts::Post;
+use crate::search::{SearchState, Target};
+use crate::theme::{Scheme, Theme};
+use crate::ui;
+use crate::vcard;
 
-    pub fn label(&self) -> &'static str {
-        match self {
-            Tab::About => "About",
-            Tab::Projects => "Projects",
-            Tab::Skills => "Skills",
-            Tab::Contact => "Contact",
-        }
-    }
+/// Lines above the body in the post reader: title, date and tags,
+/// then a blank line.
+pub const POST_HEADER_LINES: usize = 3;
 
-    pub fn index(&self) -> usize {
-        match self {
-            Tab::About => 0,
-            Tab::Projects => 1,
-            Tab::Skills => 2,
-            Tab::Contact => 3,
-        }
-    }
+/// Tab, post being read and width a page of Markdown was laid out for.
+type PageKey = (usize, Option<usize>, usize);
 
-    pub fn from_index(i: usize) -> Option<Tab> {
-        Tab::ALL.get(i).copied()
-    }
-}
+/// How long a toast stays up.
+pub const TOAST_DURATION: Duration = Duration::from_secs(2);
 
 /// Intro animation phase.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,49 +36,422 @@ pub enum IntroPhase {
     Done,
 }
 
+/// What keystrokes are routed to.
+#[derive(Debug, Clone)]
+pub enum Mode {
+    /// Navigation bindings.
+    Normal,
+    /// Typing into the search prompt.
+    Search(SearchState),
+    /// Choosing a tag to filter the project browser by. Option 0 clears
+    /// the filter; option `n` is the `n - 1`th of [`App::tag_options`].
+    TagPicker { selected: usize },
+    /// Reading the list of key bindings, scrolled by this many lines.
+    Help { scroll: usize },
+    /// Typing into the command palette.
+    Command(CommandState),
+    /// Showing the contact card as a QR code.
+    ContactCard,
+    /// Choosing a colour theme; `selected` indexes the session's
+    /// schemes.
+    ThemePicker { selected: usize },
+}
+
+/// Brief notice shown over the content, e.g. after copying.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub shown: Instant,
+}
+
+/// Output of the last command, shown in place of the footer until the
+/// next key.
+#[derive(Debug, Clone)]
+pub struct Status {
+    pub text: String,
+    pub error: bool,
+}
+
 /// Application state for a single SSH client session.
 pub struct App {
-    pub tab: Tab,
+    /// Index of the active tab in `portfolio.tabs`.
+    pub tab: usize,
     pub should_quit: bool,
     pub scroll_offset: usize,
     pub intro: IntroPhase,
+    pub mode: Mode,
+    pub status: Option<Status>,
+    pub toast: Option<Toast>,
+    /// Escape sequences for the client's terminal that are not part of
+    /// a frame, such as clipboard writes. Sent after the next render.
+    pub escapes: Vec<Vec<u8>>,
     /// Index of the currently selected project in the flat project list (Projects tab).
     pub selected_project: usize,
+    /// Tag the project browser is filtered by, if any.
+    pub project_filter: Option<String>,
+    /// Index of the currently selected entry on a timeline tab.
+    pub selected_role: usize,
+    /// Index of the currently selected post on a posts tab.
+    pub selected_post: usize,
+    /// Index of the currently selected entry on a key/value tab.
+    pub selected_entry: usize,
+    /// Post being read, if the reader is open.
+    pub open_post: Option<usize>,
+    /// Portfolio content shown to this client.
+    pub portfolio: Arc<Portfolio>,
+    /// Lines of rendered Markdown by tab, open post and width, so that
+    /// scrolling does not render the page again on every key.
+    markdown_lines: RefCell<HashMap<PageKey, usize>>,
+    /// Client terminal size in columns and rows.
+    pub size: (u16, u16),
+    /// Key bindings for normal mode.
+    pub keymap: Arc<Keymap>,
+    /// What the client's terminal can show.
+    pub caps: Capabilities,
+    /// Colour schemes to choose from, never empty; the first is the
+    /// default.
+    schemes: Arc<[Scheme]>,
+    /// Index of the colour scheme in use.
+    scheme: usize,
+    /// Styles of the colour scheme, resolved for the client's terminal.
+    pub theme: Theme,
+    /// Draw with ASCII only, for terminals that are not set up for UTF-8.
+    pub ascii: bool,
+    /// Name the visitor logged in as, reported by `whoami`.
+    pub user: String,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(portfolio: Arc<Portfolio>, keymap: Arc<Keymap>, schemes: Arc<[Scheme]>) -> Self {
         Self {
-            tab: Tab::About,
+            tab: 0,
             should_quit: false,
             scroll_offset: 0,
             intro: IntroPhase::Typing { chars_shown: 0 },
+            mode: Mode::Normal,
+            status: None,
+            toast: None,
+            escapes: Vec::new(),
             selected_project: 0,
+            project_filter: None,
+            selected_role: 0,
+            selected_post: 0,
+            selected_entry: 0,
+            open_post: None,
+            portfolio,
+            markdown_lines: RefCell::default(),
+            size: (80, 24),
+            keymap,
+            caps: Capabilities::default(),
+            schemes,
+            scheme: 0,
+            theme: Theme::default(),
+            ascii: false,
+            user: "guest".to_string(),
+        }
+    }
+
+    // ── Terminal ───────────────────────────────────────────────
+
+    /// Take the terminal type from the client's PTY request.
+    pub fn set_term(&mut self, term: &str) {
+        self.caps.set_term(term);
+        self.restyle();
+    }
+
+    /// Take an environment variable from the client. Returns `false` if
+    /// it says nothing about the terminal.
+    pub fn set_env(&mut self, name: &str, value: &str) -> bool {
+        let used = self.caps.set_env(name, value);
+        self.restyle();
+        used
+    }
+
+    /// Pick the drawing mode once the client has said all it will about
+    /// its terminal, before the first frame. After that only the user
+    /// switches it.
+    pub fn start(&mut self) {
+        self.ascii = !self.caps.unicode;
+    }
+
+    /// Switch to the colour scheme at `index` of the session's list.
+    pub fn set_scheme(&mut self, index: usize) {
+        self.scheme = index % self.schemes.len();
+        self.restyle();
+    }
+
+    pub fn cycle_scheme(&mut self) {
+        self.set_scheme(self.scheme + 1);
+        self.show_toast(format!("Theme: {}", self.schemes[self.scheme].name));
+    }
+
+    /// Colour schemes the session can switch between.
+    pub fn schemes(&self) -> &[Scheme] {
+        &self.schemes
+    }
+
+    /// Index of the colour scheme in use.
+    pub fn scheme(&self) -> usize {
+        self.scheme
+    }
+
+    pub fn picking_theme(&self) -> Option<usize> {
+        match self.mode {
+            Mode::ThemePicker { selected } => Some(selected),
+            _ => None,
+        }
+    }
+
+    /// Open the theme picker on the theme in use.
+    pub fn open_theme_picker(&mut self) {
+        self.mode = Mode::ThemePicker {
+            selected: self.scheme,
+        };
+    }
+
+    pub fn close_theme_picker(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    pub fn theme_picker_next(&mut self) {
+        let options = self.schemes.len();
+        if let Mode::ThemePicker { selected } = &mut self.mode
+            && *selected + 1 < options
+        {
+            *selected += 1;
+        }
+    }
+
+    pub fn theme_picker_prev(&mut self) {
+        if let Mode::ThemePicker { selected } = &mut self.mode {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Switch to the theme highlighted in the picker and close it.
+    pub fn confirm_theme_picker(&mut self) {
+        let Some(selected) = self.picking_theme() else {
+            return;
+        };
+        self.mode = Mode::Normal;
+        self.set_scheme(selected);
+    }
+
+    fn restyle(&mut self) {
+        self.theme = Theme::new(&self.schemes[self.scheme], self.caps.colors);
+    }
+
+    pub fn toggle_ascii(&mut self) {
+        self.ascii = !self.ascii;
+        let mode = if self.ascii { "ASCII-only" } else { "Unicode" };
+        self.show_toast(format!("{mode} drawing"));
+    }
+
+    /// Perform a normal-mode action.
+    pub fn apply(&mut self, action: Action) {
+        let (_, content_h) = self.content_viewport();
+        let has_selection = self.has_selection();
+
+        match action {
+            Action::Quit => self.quit(),
+            Action::NextTab => self.next_tab(),
+            Action::PrevTab => self.prev_tab(),
+            Action::Up => {
+                if has_selection {
+                    self.select_prev();
+                } else {
+                    self.scroll_up();
+                }
+            }
+            Action::Down => {
+                if has_selection {
+                    self.select_next();
+                } else {
+                    let total = self.content_line_count();
+                    self.scroll_down(total, content_h);
+                }
+            }
+            Action::PageUp => self.page_up(),
+            Action::PageDown => self.page_down(),
+            Action::Top => {
+                if has_selection {
+                    self.select_first();
+                } else {
+                    self.scroll_offset = 0;
+                }
+            }
+            Action::Bottom => {
+                if has_selection {
+                    self.select_last();
+                } else {
+                    let total = self.content_line_count();
+                    if total > content_h {
+                        self.scroll_offset = total - content_h;
+                    }
+                }
+            }
+            Action::GoToTab(idx) => self.go_to_tab(idx),
+            Action::Search => self.start_search(),
+            Action::Filter => self.open_tag_picker(),
+            Action::Open => self.open_selected_post(),
+            Action::Back => self.close_post(),
+            Action::Help => self.open_help(),
+            Action::Command => self.start_command(),
+            Action::Copy => self.copy_selected(),
+            Action::ContactCard => self.open_contact_card(),
+            Action::ToggleAscii => self.toggle_ascii(),
+            Action::CycleTheme => self.cycle_scheme(),
+            Action::PickTheme => self.open_theme_picker(),
+        }
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.size = (width, height);
+    }
+
+    /// Width and height of the text inside the content block, mirroring
+    /// the layout in `ui::render`.
+    pub fn content_viewport(&self) -> (usize, usize) {
+        let (width, height) = self.size;
+        let header = self.portfolio.banner.len() as u16 + 4;
+        let chrome_h = header + 3 + 1; // header, tab bar, footer
+        let inner_w = width.saturating_sub(2 + 4); // borders + padding
+        let inner_h = height.saturating_sub(chrome_h + 2 + 2);
+        (inner_w as usize, inner_h as usize)
+    }
+
+    // ── Tabs ───────────────────────────────────────────────────
+
+    pub fn tab_count(&self) -> usize {
+        self.portfolio.tabs.len()
+    }
+
+    /// The active tab definition, if the portfolio has any tabs.
+    pub fn current_tab(&self) -> Option<&Tab> {
+        self.portfolio.tabs.get(self.tab)
+    }
+
+    /// Whether up/down move a selection on the active tab instead of
+    /// scrolling it.
+    pub fn has_selection(&self) -> bool {
+        match self.current_tab() {
+            Some(Tab::Projects { .. } | Tab::Timeline { .. } | Tab::KeyValue { .. }) => true,
+            // The post list selects; the reader scrolls
+            Some(Tab::Posts { .. }) => self.open_post.is_none(),
+            _ => false,
+        }
+    }
+
+    /// Timeline entries of the active tab (empty unless it is a timeline).
+    pub fn roles(&self) -> &[Role] {
+        match self.current_tab() {
+            Some(Tab::Timeline { entries, .. }) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Entries of the active tab (empty unless it is a key/value tab).
+    pub fn entries(&self) -> &[ContactEntry] {
+        match self.current_tab() {
+            Some(Tab::KeyValue { entries, .. }) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Posts of the active tab (empty unless it is a posts tab).
+    pub fn posts(&self) -> &[Post] {
+        match self.current_tab() {
+            Some(Tab::Posts { posts, .. }) => posts,
+            _ => &[],
+        }
+    }
+
+    /// The post open in the reader, if any.
+    pub fn reading(&self) -> Option<&Post> {
+        self.posts().get(self.open_post?)
+    }
+
+    /// Project categories of the active tab (empty unless it is a
+    /// project browser).
+    pub fn categories(&self) -> &[ProjectCategory] {
+        match self.current_tab() {
+            Some(Tab::Projects { categories, .. }) => categories,
+            _ => &[],
         }
     }
 
     pub fn next_tab(&mut self) {
-        let idx = self.tab.index();
-        let next = (idx + 1) % Tab::ALL.len();
-        self.tab = Tab::ALL[next];
-        self.scroll_offset = 0;
+        let count = self.tab_count();
+        if count > 0 {
+            self.go_to_tab((self.tab + 1) % count);
+        }
     }
 
     pub fn prev_tab(&mut self) {
-        let idx = self.tab.index();
-        let prev = if idx == 0 {
-            Tab::ALL.len() - 1
-        } else {
-            idx - 1
-        };
-        self.tab = Tab::ALL[prev];
-        self.scroll_offset = 0;
+        let count = self.tab_count();
+        if count > 0 {
+            let prev = if self.tab == 0 { count - 1 } else { self.tab - 1 };
+            self.go_to_tab(prev);
+        }
     }
 
     pub fn go_to_tab(&mut self, idx: usize) {
-        if let Some(tab) = Tab::from_index(idx) {
-            self.tab = tab;
+        if idx < self.tab_count() {
+            self.tab = idx;
             self.scroll_offset = 0;
+            self.open_post = None;
+            // Several project browsers may share the selection and filter
+            if portfolio::total_project_count(self.categories(), self.filter()) == 0 {
+                self.project_filter = None;
+            }
+            let total = portfolio::total_project_count(self.categories(), self.filter());
+            if total > 0 {
+                self.selected_project = self.selected_project.min(total - 1);
+            }
+            let roles = self.roles().len();
+            if roles > 0 {
+                self.selected_role = self.selected_role.min(roles - 1);
+            }
+            let posts = self.posts().len();
+            if posts > 0 {
+                self.selected_post = self.selected_post.min(posts - 1);
+            }
+            let entries = self.entries().len();
+            if entries > 0 {
+                self.selected_entry = self.selected_entry.min(entries - 1);
+            }
+        }
+    }
+
+    /// Switch to a newer portfolio, clamping the selection and scroll
+    /// position in case the content shrank. No-op if it is unchanged.
+    pub fn sync_portfolio(&mut self, latest: &Arc<Portfolio>) {
+        if Arc::ptr_eq(&self.portfolio, latest) {
+            return;
+        }
+        self.portfolio = latest.clone();
+        self.markdown_lines.borrow_mut().clear();
+
+        self.tab = self.tab.min(self.tab_count().saturating_sub(1));
+        if portfolio::total_project_count(self.categories(), self.filter()) == 0 {
+            self.project_filter = None;
+        }
+        let total = portfolio::total_project_count(self.categories(), self.filter());
+        self.selected_project = self.selected_project.min(total.saturating_sub(1));
+        self.selected_role = self.selected_role.min(self.roles().len().saturating_sub(1));
+        self.selected_post = self.selected_post.min(self.posts().len().saturating_sub(1));
+        self.selected_entry = self.selected_entry.min(self.entries().len().saturating_sub(1));
+        if self.reading().is_none() {
+            self.open_post = None;
+        }
+        let lines = self.content_line_count();
+        self.scroll_offset = self.scroll_offset.min(lines.saturating_sub(1));
+
+        // Results may point at content that no longer exists
+        self.refresh_search();
+        let options = self.tag_options().len();
+        if let Mode::TagPicker { selected } = &mut self.mode {
+            *selected = (*selected).min(options);
         }
     }
 
@@ -98,8 +461,13 @@ impl App {
 
     // ── Project selection (Projects tab) ──────────────────────
 
+    /// The active project tag filter.
+    pub fn filter(&self) -> Option<&str> {
+        self.project_filter.as_deref()
+    }
+
     pub fn select_next_project(&mut self) {
-        let total = content::total_project_count();
+        let total = portfolio::total_project_count(self.categories(), self.filter());
         if total > 0 && self.selected_project < total - 1 {
             self.selected_project += 1;
         }
@@ -116,12 +484,564 @@ impl App {
     }
 
     pub fn select_last_project(&mut self) {
-        let total = content::total_project_count();
+        let total = portfolio::total_project_count(self.categories(), self.filter());
         if total > 0 {
             self.selected_project = total - 1;
         }
     }
 
+    // ── Tag filter (Projects tab) ──────────────────────────────
+
+    /// Tags offered by the picker on the active tab.
+    pub fn tag_options(&self) -> Vec<String> {
+        portfolio::all_tags(self.categories())
+    }
+
+    pub fn picking_tag(&self) -> Option<usize> {
+        match self.mode {
+            Mode::TagPicker { selected } => Some(selected),
+            _ => None,
+        }
+    }
+
+    /// Open the tag picker on the current filter (project browsers only).
+    pub fn open_tag_picker(&mut self) {
+        if !matches!(self.current_tab(), Some(Tab::Projects { .. })) {
+            return;
+        }
+        let options = self.tag_options();
+        let selected = self
+            .filter()
+            .and_then(|f| options.iter().position(|t| t.eq_ignore_ascii_case(f)))
+            .map_or(0, |i| i + 1);
+        self.mode = Mode::TagPicker { selected };
+    }
+
+    pub fn close_tag_picker(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    pub fn tag_picker_next(&mut self) {
+        let options = self.tag_options().len();
+        if let Mode::TagPicker { selected } = &mut self.mode
+            && *selected < options
+        {
+            *selected += 1;
+        }
+    }
+
+    pub fn tag_picker_prev(&mut self) {
+        if let Mode::TagPicker { selected } = &mut self.mode {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Apply the tag highlighted in the picker and close it.
+    pub fn confirm_tag_picker(&mut self) {
+        let Some(selected) = self.picking_tag() else {
+            return;
+        };
+        self.mode = Mode::Normal;
+        let tag = selected
+            .checked_sub(1)
+            .and_then(|i| self.tag_options().into_iter().nth(i));
+        self.set_project_filter(tag);
+    }
+
+    /// Filter the project browser by `tag`, keeping the selected project
+    /// selected if it still matches.
+    pub fn set_project_filter(&mut self, tag: Option<String>) {
+        let categories = self.categories();
+        self.selected_project = portfolio::remap_project_index(
+            categories,
+            self.selected_project,
+            self.filter(),
+            tag.as_deref(),
+        )
+        .unwrap_or(0);
+        self.project_filter = tag;
+    }
+
+    // ── Timeline selection ─────────────────────────────────────
+
+    pub fn select_next_role(&mut self) {
+        if self.selected_role + 1 < self.roles().len() {
+            self.selected_role += 1;
+        }
+    }
+
+    pub fn select_prev_role(&mut self) {
+        self.selected_role = self.selected_role.saturating_sub(1);
+    }
+
+    pub fn select_first_role(&mut self) {
+        self.selected_role = 0;
+    }
+
+    pub fn select_last_role(&mut self) {
+        self.selected_role = self.roles().len().saturating_sub(1);
+    }
+
+    // ── Key/value selection ────────────────────────────────────
+
+    pub fn select_next_entry(&mut self) {
+        if self.selected_entry + 1 < self.entries().len() {
+            self.selected_entry += 1;
+        }
+    }
+
+    pub fn select_prev_entry(&mut self) {
+        self.selected_entry = self.selected_entry.saturating_sub(1);
+    }
+
+    pub fn select_first_entry(&mut self) {
+        self.selected_entry = 0;
+    }
+
+    pub fn select_last_entry(&mut self) {
+        self.selected_entry = self.entries().len().saturating_sub(1);
+    }
+
+    // ── Posts ──────────────────────────────────────────────────
+
+    pub fn select_next_post(&mut self) {
+        if self.selected_post + 1 < self.posts().len() {
+            self.selected_post += 1;
+        }
+    }
+
+    pub fn select_prev_post(&mut self) {
+        self.selected_post = self.selected_post.saturating_sub(1);
+    }
+
+    pub fn select_first_post(&mut self) {
+        self.selected_post = 0;
+    }
+
+    pub fn select_last_post(&mut self) {
+        self.selected_post = self.posts().len().saturating_sub(1);
+    }
+
+    /// Open the selected post in the reader (posts tab only).
+    pub fn open_selected_post(&mut self) {
+        if self.open_post.is_none() && self.selected_post < self.posts().len() {
+            self.open_post = Some(self.selected_post);
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Close the reader and go back to the post list.
+    pub fn close_post(&mut self) {
+        if self.open_post.take().is_some() {
+            self.scroll_offset = 0;
+        }
+    }
+
+    // ── Selection on the active tab ────────────────────────────
+
+    pub fn select_next(&mut self) {
+        match self.current_tab() {
+            Some(Tab::Projects { .. }) => self.select_next_project(),
+            Some(Tab::Timeline { .. }) => self.select_next_role(),
+            Some(Tab::Posts { .. }) => self.select_next_post(),
+            Some(Tab::KeyValue { .. }) => self.select_next_entry(),
+            _ => {}
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        match self.current_tab() {
+            Some(Tab::Projects { .. }) => self.select_prev_project(),
+            Some(Tab::Timeline { .. }) => self.select_prev_role(),
+            Some(Tab::Posts { .. }) => self.select_prev_post(),
+            Some(Tab::KeyValue { .. }) => self.select_prev_entry(),
+            _ => {}
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        match self.current_tab() {
+            Some(Tab::Projects { .. }) => self.select_first_project(),
+            Some(Tab::Timeline { .. }) => self.select_first_role(),
+            Some(Tab::Posts { .. }) => self.select_first_post(),
+            Some(Tab::KeyValue { .. }) => self.select_first_entry(),
+            _ => {}
+        }
+    }
+
+    pub fn select_last(&mut self) {
+        match self.current_tab() {
+            Some(Tab::Projects { .. }) => self.select_last_project(),
+            Some(Tab::Timeline { .. }) => self.select_last_role(),
+            Some(Tab::Posts { .. }) => self.select_last_post(),
+            Some(Tab::KeyValue { .. }) => self.select_last_entry(),
+            _ => {}
+        }
+    }
+
+    // ── Help overlay ───────────────────────────────────────────
+
+    /// Scroll offset of the help overlay, if it is open.
+    pub fn showing_help(&self) -> Option<usize> {
+        match self.mode {
+            Mode::Help { scroll } => Some(scroll),
+            _ => None,
+        }
+    }
+
+    pub fn open_help(&mut self) {
+        self.mode = Mode::Help { scroll: 0 };
+    }
+
+    pub fn close_help(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    /// Bindings listed by the help overlay.
+    pub fn help_sections(&self) -> Vec<HelpSection> {
+        self.keymap.help(self.tab_count())
+    }
+
+    /// Lines in the help overlay: each section's title and entries, with
+    /// a blank line between sections.
+    pub fn help_line_count(&self) -> usize {
+        let sections = self.help_sections();
+        let lines: usize = sections.iter().map(|s| 1 + s.entries.len()).sum();
+        lines + sections.len().saturating_sub(1)
+    }
+
+    pub fn help_scroll_up(&mut self) {
+        if let Mode::Help { scroll } = &mut self.mode {
+            *scroll = scroll.saturating_sub(1);
+        }
+    }
+
+    pub fn help_scroll_down(&mut self) {
+        // The overlay's border sits on the content block's padding, so
+        // it shows as many lines as the viewport
+        let (_, viewport_h) = self.content_viewport();
+        let max = self.help_line_count().saturating_sub(viewport_h);
+        if let Mode::Help { scroll } = &mut self.mode
+            && *scroll < max
+        {
+            *scroll += 1;
+        }
+    }
+
+    // ── Contact card ───────────────────────────────────────────
+
+    pub fn showing_contact_card(&self) -> bool {
+        matches!(self.mode, Mode::ContactCard)
+    }
+
+    /// Show the contact card as a QR code (key/value tabs only).
+    pub fn open_contact_card(&mut self) {
+        if matches!(self.current_tab(), Some(Tab::KeyValue { .. })) {
+            self.mode = Mode::ContactCard;
+        }
+    }
+
+    pub fn close_contact_card(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    /// The contact card, as a vCard.
+    pub fn contact_card(&self) -> String {
+        vcard::from_portfolio(&self.portfolio)
+    }
+
+    // ── Search ─────────────────────────────────────────────────
+
+    pub fn searching(&self) -> Option<&SearchState> {
+        match &self.mode {
+            Mode::Search(search) => Some(search),
+            _ => None,
+        }
+    }
+
+    fn searching_mut(&mut self) -> Option<&mut SearchState> {
+        match &mut self.mode {
+            Mode::Search(search) => Some(search),
+            _ => None,
+        }
+    }
+
+    /// Open the search prompt with an empty query.
+    pub fn start_search(&mut self) {
+        self.mode = Mode::Search(SearchState::default());
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    pub fn search_push(&mut self, c: char) {
+        if let Some(search) = self.searching_mut() {
+            search.query.push(c);
+        }
+        self.refresh_search();
+    }
+
+    pub fn search_backspace(&mut self) {
+        if let Some(search) = self.searching_mut() {
+            search.query.pop();
+        }
+        self.refresh_search();
+    }
+
+    pub fn search_clear(&mut self) {
+        if let Some(search) = self.searching_mut() {
+            search.query.clear();
+        }
+        self.refresh_search();
+    }
+
+    pub fn search_select_next(&mut self) {
+        if let Some(search) = self.searching_mut() {
+            search.select_next();
+        }
+    }
+
+    pub fn search_select_prev(&mut self) {
+        if let Some(search) = self.searching_mut() {
+            search.select_prev();
+        }
+    }
+
+    fn refresh_search(&mut self) {
+        let (width, _) = self.content_viewport();
+        let portfolio = self.portfolio.clone();
+        if let Some(search) = self.searching_mut() {
+            search.refresh(&portfolio, width);
+        }
+    }
+
+    /// Close the prompt and jump to the highlighted result: its tab, and
+    /// the project, entry, post or line it was found in.
+    pub fn confirm_search(&mut self) {
+        let Mode::Search(search) = std::mem::replace(&mut self.mode, Mode::Normal) else {
+            return;
+        };
+        if let Some(hit) = search.selected_hit() {
+            self.jump_to(hit.tab, hit.target);
+        }
+    }
+
+    /// Go to `tab` and select or scroll to `target` on it.
+    fn jump_to(&mut self, tab: usize, target: Target) {
+        self.go_to_tab(tab);
+        match target {
+            Target::Project(i) => {
+                // Results index all projects; drop a filter that hides this one
+                let categories = self.categories();
+                let filtered = portfolio::remap_project_index(categories, i, None, self.filter());
+                if filtered.is_none() {
+                    self.project_filter = None;
+                }
+                self.selected_project = filtered.unwrap_or(i);
+            }
+            Target::Role(i) => self.selected_role = i,
+            Target::Post(i) => self.selected_post = i,
+            Target::Line(n) => {
+                let (_, viewport_h) = self.content_viewport();
+                let max = self.content_line_count().saturating_sub(viewport_h);
+                self.scroll_offset = n.min(max);
+            }
+            Target::Tab => {}
+        }
+    }
+
+    // ── Clipboard ──────────────────────────────────────────────
+
+    /// What `y` copies on the active tab: the selected project's URL or
+    /// the selected entry's value.
+    pub fn copyable(&self) -> Option<&str> {
+        match self.current_tab() {
+            Some(Tab::Projects { categories, .. }) => {
+                let (_, project) = portfolio::get_project_by_flat_index(
+                    categories,
+                    self.selected_project,
+                    self.filter(),
+                )?;
+                Some(project.url.as_str()).filter(|url| !url.is_empty())
+            }
+            Some(Tab::KeyValue { entries, .. }) => {
+                entries.get(self.selected_entry).map(|e| e.value.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Copy the selection to the client's clipboard.
+    pub fn copy_selected(&mut self) {
+        let Some(text) = self.copyable().map(str::to_string) else {
+            return;
+        };
+        self.escapes.push(clipboard::copy_sequence(&text));
+        self.show_toast(format!("Copied {text}"));
+    }
+
+    pub fn take_escapes(&mut self) -> Vec<Vec<u8>> {
+        std::mem::take(&mut self.escapes)
+    }
+
+    pub fn show_toast(&mut self, text: String) {
+        self.toast = Some(Toast {
+            text,
+            shown: Instant::now(),
+        });
+    }
+
+    /// Drop the toast once it has been up for [`TOAST_DURATION`].
+    /// Returns `true` if it was dropped (needs re-render).
+    pub fn expire_toast(&mut self) -> bool {
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|t| t.shown.elapsed() >= TOAST_DURATION)
+        {
+            self.toast = None;
+            return true;
+        }
+        false
+    }
+
+    // ── Command palette ────────────────────────────────────────
+
+    pub fn command_prompt(&self) -> Option<&CommandState> {
+        match &self.mode {
+            Mode::Command(command) => Some(command),
+            _ => None,
+        }
+    }
+
+    fn command_prompt_mut(&mut self) -> Option<&mut CommandState> {
+        match &mut self.mode {
+            Mode::Command(command) => Some(command),
+            _ => None,
+        }
+    }
+
+    /// Open the command palette with an empty prompt.
+    pub fn start_command(&mut self) {
+        self.mode = Mode::Command(CommandState::default());
+    }
+
+    pub fn cancel_command(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    pub fn command_push(&mut self, c: char) {
+        if let Some(command) = self.command_prompt_mut() {
+            command.input.push(c);
+            command.candidates.clear();
+        }
+    }
+
+    /// Delete the last character, closing the palette if there is none.
+    pub fn command_backspace(&mut self) {
+        if let Some(command) = self.command_prompt_mut() {
+            if command.input.pop().is_none() {
+                self.cancel_command();
+            } else {
+                command.candidates.clear();
+            }
+        }
+    }
+
+    pub fn command_clear(&mut self) {
+        if let Some(command) = self.command_prompt_mut() {
+            command.input.clear();
+            command.candidates.clear();
+        }
+    }
+
+    pub fn complete_command(&mut self) {
+        let portfolio = self.portfolio.clone();
+        let schemes = self.schemes.clone();
+        if let Some(command) = self.command_prompt_mut() {
+            command.complete(&portfolio, &schemes);
+        }
+    }
+
+    /// Close the palette and run what was typed, reporting the outcome
+    /// in the status line.
+    pub fn run_command(&mut self) {
+        let Mode::Command(prompt) = std::mem::replace(&mut self.mode, Mode::Normal) else {
+            return;
+        };
+        match command::parse(&self.portfolio, &self.schemes, &prompt.input) {
+            Ok(Command::Go { tab, target }) => self.open_target(tab, target),
+            Ok(Command::Theme(scheme)) => {
+                self.set_scheme(scheme);
+                let name = self.schemes[self.scheme].name.clone();
+                self.set_status(format!("theme: {name}"), false);
+            }
+            Ok(Command::Whoami) => self.set_status(self.user.clone(), false),
+            Ok(Command::Help) => self.set_status(command::usage(), false),
+            Err(e) => self.set_status(e.to_string(), true),
+        }
+    }
+
+    /// Go to `target` on `tab`, opening it if it is a post.
+    fn open_target(&mut self, tab: usize, target: Target) {
+        self.jump_to(tab, target);
+        if let Target::Post(_) = target {
+            self.open_selected_post();
+        }
+    }
+
+    /// Follow a deep link: land on the tab, project or post called
+    /// `name` (or the only one starting with it), skipping the intro.
+    /// Returns `false` if nothing is called that, leaving the app as it
+    /// was.
+    pub fn open_link(&mut self, name: &str) -> bool {
+        let found = command::resolve(&self.portfolio, name).ok().flatten();
+        self.follow_link(found)
+    }
+
+    /// Follow the deep link in an SSH username. Only a full name counts,
+    /// so a login that happens to start a title is not taken for one.
+    pub fn open_user_link(&mut self, user: &str) -> bool {
+        let found = command::resolve_exact(&self.portfolio, user);
+        self.follow_link(found)
+    }
+
+    fn follow_link(&mut self, found: Option<(usize, Target)>) -> bool {
+        let Some((tab, target)) = found else {
+            return false;
+        };
+        self.open_target(tab, target);
+        self.skip_intro();
+        true
+    }
+
+    fn set_status(&mut self, text: String, error: bool) {
+        self.status = Some(Status { text, error });
+    }
+
+    /// Select the list item at `idx` on the active tab. Clicking the
+    /// post that is already selected opens it.
+    pub fn click_item(&mut self, idx: usize) {
+        match self.current_tab() {
+            Some(Tab::Projects { .. }) => {
+                let total = portfolio::total_project_count(self.categories(), self.filter());
+                if idx < total {
+                    self.selected_project = idx;
+                }
+            }
+            Some(Tab::Timeline { .. }) if idx < self.roles().len() => self.selected_role = idx,
+            Some(Tab::KeyValue { .. }) if idx < self.entries().len() => self.selected_entry = idx,
+            Some(Tab::Posts { .. }) if idx < self.posts().len() => {
+                if self.selected_post == idx {
+                    self.open_selected_post();
+                }
+                self.selected_post = idx;
+            }
+            _ => {}
+        }
+    }
+
     // ── Scrolling ──────────────────────────────────────────────
 
     pub fn scroll_up(&mut self) {
@@ -137,14 +1057,48 @@ impl App {
         }
     }
 
+    /// Scroll up a screenful, or move the selection up half of one.
+    pub fn page_up(&mut self) {
+        let (_, viewport_h) = self.content_viewport();
+        if self.has_selection() {
+            for _ in 0..(viewport_h / 2).max(1) {
+                self.select_prev();
+            }
+        } else {
+            self.scroll_offset = self.scroll_offset.saturating_sub(viewport_h);
+        }
+    }
+
+    /// Scroll down a screenful, or move the selection down half of one.
+    pub fn page_down(&mut self) {
+        let (_, viewport_h) = self.content_viewport();
+        if self.has_selection() {
+            for _ in 0..(viewport_h / 2).max(1) {
+                self.select_next();
+            }
+        } else {
+            let max = self.content_line_count().saturating_sub(viewport_h);
+            self.scroll_offset = (self.scroll_offset + viewport_h).min(max);
+        }
+    }
+
     /// Returns the total number of content lines for the current tab.
     pub fn content_line_count(&self) -> usize {
-        match self.tab {
-            Tab::About => content::ABOUT_LINES.len(),
-            Tab::Projects => content::total_project_lines(),
-            Tab::Skills => {
+        match self.current_tab() {
+            Some(Tab::Text { body, .. }) => self.markdown_line_count(body),
+            Some(Tab::Projects { categories, .. }) => {
+                portfolio::total_project_lines(categories, self.filter())
+            }
+            // Two lines per entry plus a connector line between entries
+            Some(Tab::Timeline { entries, .. }) => (entries.len() * 3).saturating_sub(1),
+            Some(Tab::Posts { .. }) => match self.reading() {
+                Some(post) => POST_HEADER_LINES + self.markdown_line_count(&post.body),
+                // Title and date line per post
+                None => self.posts().len() * 2,
+            },
+            Some(Tab::Skills { groups, .. }) => {
                 let mut lines = 0;
-                for (i, _group) in content::SKILLS.iter().enumerate() {
+                for (i, _group) in groups.iter().enumerate() {
                     if i > 0 {
                         lines += 1; // blank separator
                     }
@@ -153,13 +1107,35 @@ impl App {
                 }
                 lines
             }
-            Tab::Contact => {
-                // intro + blank + entries + blank + outro
-                1 + 1 + content::CONTACT_ENTRIES.len() + 1 + 1
+            Some(Tab::KeyValue {
+                intro,
+                entries,
+                outro,
+                ..
+            }) => {
+                // Wrapped intro + blank + one line per entry + blank +
+                // wrapped outro, as in `ui::render_key_value`
+                let (width, _) = self.content_viewport();
+                let intro = ui::wrap_words(intro, width).len();
+                let outro = ui::wrap_words(outro, width).len();
+                intro + 1 + entries.len() + 1 + outro
             }
+            None => 0,
         }
     }
 
+    /// Lines `source`, the page of the current tab or post, takes at the
+    /// content width.
+    fn markdown_line_count(&self, source: &str) -> usize {
+        let (width, _) = self.content_viewport();
+        let key = (self.tab, self.reading().and(self.open_post), width);
+        *self
+            .markdown_lines
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| markdown::render(source, width, &self.theme).len())
+    }
+
     // ── Intro animation ────────────────────────────────────────
 
     /// Advance the typewriter animation by `chars` characters.
@@ -167,7 +1143,7 @@ impl App {
     pub fn advance_intro(&mut self, chars: usize) -> bool {
         match self.intro {
             IntroPhase::Typing { chars_shown } => {
-                let total = content::banner_char_count();
+                let total = self.portfolio.banner_char_count();
                 let next = (chars_shown + chars).min(total);
                 if next >= total {
                     self.intro = IntroPhase::Pause { ticks_remaining: 8 };
@@ -199,3 +1175,158 @@ impl App {
         matches!(self.intro, IntroPhase::Done)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    /// A single projects tab holding `(name, tech)` projects.
+    fn portfolio(projects: &[(&str, &str)]) -> Arc<Portfolio> {
+        let projects: Vec<String> = projects
+            .iter()
+            .map(|(name, tech)| {
+                format!(
+                    "{{ name = \"{name}\", description = \"\", tech = \"{tech}\", url = \"\" }}"
+                )
+            })
+            .collect();
+        let raw = format!(
+            "[[tabs]]\nkind = \"projects\"\ntitle = \"Projects\"\n\
+             [[tabs.categories]]\nname = \"Apps\"\nprojects = [{}]\n",
+            projects.join(", ")
+        );
+        Arc::new(Portfolio::parse(&raw).unwrap())
+    }
+
+    fn app(portfolio: Arc<Portfolio>) -> App {
+        App::new(
+            portfolio,
+            Arc::new(Keymap::default()),
+            theme::BUNDLED.into(),
+        )
+    }
+
+    #[test]
+    fn sync_clamps_the_selection_when_projects_are_removed() {
+        let mut app = app(portfolio(&[("a", ""), ("b", ""), ("c", "")]));
+        app.selected_project = 2;
+        app.scroll_offset = 10;
+
+        app.sync_portfolio(&portfolio(&[("a", "")]));
+        assert_eq!(app.selected_project, 0);
+        assert_eq!(app.scroll_offset, app.content_line_count() - 1);
+    }
+
+    #[test]
+    fn sync_keeps_the_selected_position_when_projects_are_reordered() {
+        let mut app = app(portfolio(&[("a", ""), ("b", ""), ("c", "")]));
+        app.selected_project = 1;
+        app.scroll_offset = 4;
+
+        app.sync_portfolio(&portfolio(&[("c", ""), ("b", ""), ("a", "")]));
+        assert_eq!(app.selected_project, 1);
+        assert_eq!(app.scroll_offset, 4);
+    }
+
+    #[test]
+    fn sync_clamps_the_tab_when_tabs_are_removed() {
+        let mut app = app(Arc::new(Portfolio::builtin()));
+        app.tab = 3;
+        app.sync_portfolio(&portfolio(&[("a", "")]));
+        assert_eq!(app.tab, 0);
+    }
+
+    #[test]
+    fn sync_keeps_state_when_nothing_changed() {
+        let latest = portfolio(&[("a", ""), ("b", ""), ("c", "")]);
+        let mut app = app(latest.clone());
+        app.selected_project = 2;
+        app.sync_portfolio(&latest);
+        assert_eq!(app.selected_project, 2);
+    }
+
+    #[test]
+    fn sync_drops_a_filter_that_no_longer_matches() {
+        let mut app = app(portfolio(&[("a", "Rust"), ("b", "Go")]));
+        app.set_project_filter(Some("go".to_string()));
+        app.sync_portfolio(&portfolio(&[("a", "Rust")]));
+        assert_eq!(app.filter(), None);
+        assert_eq!(app.selected_project, 0);
+    }
+
+    #[test]
+    fn filtering_keeps_the_selected_project_if_it_matches() {
+        let mut app = app(portfolio(&[("a", "Rust"), ("b", "Go"), ("c", "Rust, Go")]));
+        app.selected_project = 2;
+        app.set_project_filter(Some("go".to_string()));
+        assert_eq!(app.selected_project, 1);
+        app.set_project_filter(Some("rust".to_string()));
+        assert_eq!(app.selected_project, 1);
+        app.set_project_filter(None);
+        assert_eq!(app.selected_project, 2);
+
+        app.selected_project = 1;
+        app.set_project_filter(Some("rust".to_string()));
+        assert_eq!(app.selected_project, 0);
+    }
+
+    #[test]
+    fn copying_sends_the_selection_to_the_clipboard() {
+        let contact = Portfolio::parse(
+            "[[tabs]]\nkind = \"key_value\"\ntitle = \"Contact\"\n\
+             entries = [{ label = \"Email\", value = \"me@a.dev\" }, { label = \"Web\", value = \"a.dev\" }]\n",
+        )
+        .unwrap();
+        let mut app = app(Arc::new(contact));
+        app.select_next();
+        app.copy_selected();
+        assert_eq!(app.take_escapes(), [clipboard::copy_sequence("a.dev")]);
+        assert_eq!(app.toast.map(|t| t.text).as_deref(), Some("Copied a.dev"));
+    }
+
+    #[test]
+    fn projects_without_a_url_copy_nothing() {
+        let mut app = app(portfolio(&[("a", "")]));
+        assert_eq!(app.copyable(), None);
+        app.copy_selected();
+        assert!(app.take_escapes().is_empty());
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn usernames_need_the_whole_name_but_commands_do_not() {
+        let mut app = app(Arc::new(Portfolio::builtin()));
+        assert!(!app.open_user_link("proj"));
+        assert_eq!(app.tab, 0);
+        assert!(app.open_user_link("PROJECTS"));
+        assert_eq!(app.tab, 1);
+
+        app.tab = 0;
+        assert!(app.open_link("proj"));
+        assert_eq!(app.tab, 1);
+    }
+
+    #[test]
+    fn key_value_line_count_follows_the_wrapped_intro() {
+        let raw = format!(
+            "[[tabs]]\nkind = \"key_value\"\ntitle = \"Contact\"\nintro = \"{}\"\n\
+             entries = [{{ label = \"Email\", value = \"me@a.dev\" }}]\n",
+            "word ".repeat(40)
+        );
+        let mut app = app(Arc::new(Portfolio::parse(&raw).unwrap()));
+        // 74 columns fit 15 words a line
+        app.size = (80, 40);
+        assert_eq!(app.content_line_count(), 3 + 1 + 1 + 1 + 1);
+    }
+
+    #[test]
+    fn whoami_reports_the_login_name() {
+        let mut app = app(Arc::new(Portfolio::builtin()));
+        app.user = "visitor".to_string();
+        app.start_command();
+        "whoami".chars().for_each(|c| app.command_push(c));
+        app.run_command();
+        assert_eq!(app.status.map(|s| s.text).as_deref(), Some("visitor"));
+    }
+}
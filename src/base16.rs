@@ -0,0 +1,239 @@
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use ratatui::style::Color;
+
+use crate::theme::{Palette, Scheme};
+
+// ── Base16 schemes ─────────────────────────────────────────────
+//
+// Colour themes can be added by pointing the server at a directory of
+// Base16 (or Base24) scheme files, as published by tinted-theming:
+//
+//     scheme: "Gruvbox dark, hard"
+//     author: "Dawid Kurek"
+//     base00: "1d2021"
+//     base01: "3c3836"
+//     ...
+//
+// The newer layout, with `name:` for `scheme:` and the colours under
+// `palette:` and `#`-prefixed, reads the same. Only flat `key: value`
+// lines are looked at, and only base00 to base0F are used. Each scheme
+// is named by its `scheme:` or `name:` field, or after its file if it
+// has neither, and its colours are given the roles the Base16 styling
+// guidelines suggest:
+//
+//     base00  background              base0D  headings, selections
+//     base02  borders                 base0C  links
+//     base03  hints and labels        base0A  matches and filters
+//     base04  secondary text          base0B  notices
+//     base05  body text               base08  errors
+
+/// Read every `*.yaml` and `*.yml` file in `dir`, sorted by name. A
+/// file that cannot be read or is not a scheme is logged and skipped.
+pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Scheme>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+
+    let mut schemes = Vec::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => {
+                log::warn!("Skipping a theme in {}: {e}", dir.display());
+                continue;
+            }
+        };
+        if !path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            continue;
+        }
+        match load(&path) {
+            Ok(scheme) => schemes.push(scheme),
+            Err(e) => log::warn!("Skipping theme {}: {e:#}", path.display()),
+        }
+    }
+
+    schemes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(schemes)
+}
+
+fn load(path: &Path) -> anyhow::Result<Scheme> {
+    let raw = std::fs::read_to_string(path)?;
+    let (name, base) = parse(&raw)?;
+    let name = name
+        .or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+    Ok(Scheme::new(name, palette(&base)))
+}
+
+/// The name, if the file gives one, and the sixteen base colours of a
+/// scheme file.
+fn parse(raw: &str) -> anyhow::Result<(Option<String>, [Color; 16])> {
+    let mut name = None;
+    let mut base = [None; 16];
+    for line in raw.lines() {
+        // The name is a top-level field; `palette:` entries are indented
+        let top_level = !line.starts_with(char::is_whitespace);
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if top_level && name.is_none() && (key == "scheme" || key == "name") {
+            name = Some(string(value)).filter(|n| !n.is_empty());
+            continue;
+        }
+        // base00 to base0F; Base24's base10 to base17 are left out
+        let Some(i) = key
+            .trim()
+            .strip_prefix("base")
+            .filter(|n| n.len() == 2)
+            .and_then(|n| usize::from_str_radix(n, 16).ok())
+            .filter(|&i| i < base.len())
+        else {
+            continue;
+        };
+        base[i] = Some(color(value).with_context(|| format!("in {}", key.trim()))?);
+    }
+
+    let mut colors = [Color::Reset; 16];
+    for (i, color) in base.into_iter().enumerate() {
+        match color {
+            Some(color) => colors[i] = color,
+            None => bail!("missing base{i:02X}"),
+        }
+    }
+    Ok((name, colors))
+}
+
+/// Parse a string value like `"Gruvbox dark, hard"` or `Nord  # comment`.
+fn string(value: &str) -> String {
+    let value = value.trim();
+    let quoted = ['"', '\''].into_iter().find_map(|quote| {
+        value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.split_once(quote))
+            .map(|(inner, _)| inner)
+    });
+    match quoted {
+        Some(inner) => inner.trim().to_string(),
+        None => value
+            .split(" #")
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Parse a colour value like `"1d2021"` or `"#1d2021"  # comment`.
+fn color(value: &str) -> anyhow::Result<Color> {
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    let hex = value.trim_matches(['"', '\'']).trim_start_matches('#');
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+    };
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok(Color::Rgb(r, g, b)),
+        _ => bail!("expected a hex colour like \"1d2021\", found {value}"),
+    }
+}
+
+fn palette(base: &[Color; 16]) -> Palette {
+    Palette {
+        background: base[0x00],
+        text: base[0x05],
+        text_dim: base[0x04],
+        text_muted: base[0x03],
+        border: base[0x02],
+        accent: base[0x0D],
+        link: base[0x0C],
+        highlight: base[0x0A],
+        success: base[0x0B],
+        error: base[0x08],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// base00 to base0F, numbered so each colour can be told apart.
+    fn colors(prefix: &str) -> String {
+        (0..16)
+            .map(|i| format!("{prefix}base{i:02X}: \"#0000{i:02x}\"\n"))
+            .collect()
+    }
+
+    #[test]
+    fn reads_the_classic_layout() {
+        let raw = format!(
+            "scheme: \"Gruvbox dark, hard\"  # a comment\nauthor: \"Dawid Kurek\"\n{}",
+            colors("")
+        );
+        let (name, base) = parse(&raw).unwrap();
+        assert_eq!(name.as_deref(), Some("Gruvbox dark, hard"));
+        assert_eq!(base[0x0C], Color::Rgb(0, 0, 0x0c));
+        assert_eq!(palette(&base).link, Color::Rgb(0, 0, 0x0c));
+    }
+
+    #[test]
+    fn reads_the_palette_layout() {
+        let raw = format!(
+            "system: \"base24\"\nname: 'Tomorrow Night'\npalette:\n{}  base10: \"#ffffff\"\n",
+            colors("  ")
+        );
+        let (name, base) = parse(&raw).unwrap();
+        assert_eq!(name.as_deref(), Some("Tomorrow Night"));
+        assert_eq!(base[0x0F], Color::Rgb(0, 0, 0x0f));
+    }
+
+    #[test]
+    fn a_file_without_a_name_is_left_to_its_file_name() {
+        let (name, _) = parse(&colors("")).unwrap();
+        assert_eq!(name, None);
+    }
+
+    #[test]
+    fn a_missing_colour_is_an_error() {
+        let raw = colors("").replace("base0A", "# base0A");
+        let error = parse(&raw).unwrap_err().to_string();
+        assert_eq!(error, "missing base0A");
+    }
+
+    #[test]
+    fn a_malformed_colour_is_an_error() {
+        let raw = colors("").replace("#000003", "red");
+        let error = format!("{:#}", parse(&raw).unwrap_err());
+        assert!(error.starts_with("in base03:"), "{error}");
+        assert!(color("\"1d202\"").is_err());
+        assert!(color("\"1d2g21\"").is_err());
+    }
+
+    #[test]
+    fn bad_files_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("base16-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("good.yaml"), colors("")).unwrap();
+        std::fs::write(dir.join("bad.yaml"), "scheme: \"Bad\"\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a scheme").unwrap();
+
+        let schemes = load_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let names: Vec<String> = schemes
+            .unwrap()
+            .iter()
+            .map(|s| s.name.to_string())
+            .collect();
+        assert_eq!(names, ["good"]);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use ratatui::style::Color;
+
+use crate::hyperlink;
+
+// ── Terminal capabilities ──────────────────────────────────────
+//
+// What a client's terminal can show, pieced together from what its SSH
+// client tells us: TERM in the PTY request, and COLORTERM, NO_COLOR,
+// LANG and LC_* in env requests. Env requests are only sent for the
+// variables the client's `SendEnv` lists (LANG and LC_* on most
+// systems), and they arrive after the PTY request, so the profile is
+// rebuilt as each one comes in.
+
+/// Environment variables that feed into the profile.
+const ENV_VARS: &[&str] = &["COLORTERM", "NO_COLOR", "LANG", "LC_ALL", "LC_CTYPE"];
+
+/// Terminals known to take 24-bit colour even without COLORTERM, which
+/// is rarely passed over SSH.
+const TRUECOLOR_TERMS: &[&str] = &[
+    "alacritty",
+    "contour",
+    "foot",
+    "wezterm",
+    "xterm-ghostty",
+    "xterm-kitty",
+];
+
+/// How many colours a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colours at all, only attributes like bold.
+    Mono,
+    /// The 16 named ANSI colours.
+    Ansi16,
+    /// The xterm 256-colour palette.
+    Ansi256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+/// What a client's terminal supports.
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub colors: ColorDepth,
+    /// Whether the terminal can show characters beyond ASCII, such as
+    /// box drawing and block elements.
+    pub unicode: bool,
+    /// Whether the terminal gets OSC 8 hyperlinks.
+    pub hyperlinks: bool,
+    term: String,
+    env: HashMap<String, String>,
+}
+
+impl Default for Capabilities {
+    /// What to assume before the client has said anything.
+    fn default() -> Self {
+        let mut caps = Self {
+            colors: ColorDepth::Ansi16,
+            unicode: true,
+            hyperlinks: false,
+            term: String::new(),
+            env: HashMap::new(),
+        };
+        caps.detect();
+        caps
+    }
+}
+
+impl Capabilities {
+    /// Take the terminal type from a PTY request.
+    pub fn set_term(&mut self, term: &str) {
+        self.term = term.to_string();
+        self.detect();
+    }
+
+    /// Take a variable from an env request. Returns `false` for
+    /// variables that say nothing about the terminal.
+    pub fn set_env(&mut self, name: &str, value: &str) -> bool {
+        if !ENV_VARS.contains(&name) {
+            return false;
+        }
+        self.env.insert(name.to_string(), value.to_string());
+        self.detect();
+        true
+    }
+
+    fn detect(&mut self) {
+        let term = self.term.to_ascii_lowercase();
+        let var = |name: &str| self.env.get(name).filter(|v| !v.is_empty());
+
+        self.colors = if var("NO_COLOR").is_some() || term == "dumb" {
+            ColorDepth::Mono
+        } else if var("COLORTERM").is_some_and(|c| c == "truecolor" || c == "24bit")
+            || term.ends_with("-direct")
+            || TRUECOLOR_TERMS.iter().any(|t| term.starts_with(t))
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term.starts_with("vt") {
+            // Hardware terminals and their emulations, vt100 to vt520
+            ColorDepth::Mono
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        // The first of these that is set decides, as with setlocale(3)
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"].into_iter().find_map(var);
+        self.unicode = match locale {
+            Some(locale) => {
+                let locale = locale.to_ascii_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            }
+            None => !(term == "dumb" || term == "ansi" || term.starts_with("vt")),
+        };
+
+        self.hyperlinks = hyperlink::supported(&term);
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colors = match self.colors {
+            ColorDepth::Mono => "no colours",
+            ColorDepth::Ansi16 => "16 colours",
+            ColorDepth::Ansi256 => "256 colours",
+            ColorDepth::TrueColor => "truecolor",
+        };
+        let term = if self.term.is_empty() {
+            "unknown"
+        } else {
+            &self.term
+        };
+        write!(f, "{term}, {colors}")?;
+        if self.unicode {
+            write!(f, ", Unicode")?;
+        }
+        if self.hyperlinks {
+            write!(f, ", hyperlinks")?;
+        }
+        Ok(())
+    }
+}
+
+impl ColorDepth {
+    /// The closest colour to `color` this depth can show.
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) | (_, Color::Reset) => color,
+            (ColorDepth::Mono, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) => {
+                let (r, g, b) = indexed_rgb(i);
+                nearest_16(r, g, b)
+            }
+            (ColorDepth::Ansi16, _) => color,
+        }
+    }
+}
+
+// ── Colour approximation ───────────────────────────────────────
+
+/// The 16 named colours, with xterm's default RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6×6×6 colour cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// Closest entry of the colour cube or the grey ramp (232-255).
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(c))
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+
+    let average = (r as usize + g as usize + b as usize) / 3;
+    let grey = (232 + (average.saturating_sub(8) / 10).min(23)) as u8;
+
+    if distance((r, g, b), indexed_rgb(grey)) < distance((r, g, b), indexed_rgb(cube)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+/// RGB value of a 256-colour palette entry.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI16[i as usize].1,
+        16..=231 => {
+            let i = (i - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + (i - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Squared distance between two colours, weighted for how the eye
+/// sees them.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    2 * d(a.0, b.0) + 4 * d(a.1, b.1) + 3 * d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caps(term: &str, env: &[(&str, &str)]) -> Capabilities {
+        let mut caps = Capabilities::default();
+        caps.set_term(term);
+        for (name, value) in env {
+            assert!(caps.set_env(name, value), "{name} was refused");
+        }
+        caps
+    }
+
+    #[test]
+    fn colour_depth_comes_from_term_and_colorterm() {
+        assert_eq!(Capabilities::default().colors, ColorDepth::Ansi16);
+        assert_eq!(caps("xterm", &[]).colors, ColorDepth::Ansi16);
+        assert_eq!(caps("xterm-256color", &[]).colors, ColorDepth::Ansi256);
+        assert_eq!(caps("xterm-kitty", &[]).colors, ColorDepth::TrueColor);
+        assert_eq!(caps("xterm-direct", &[]).colors, ColorDepth::TrueColor);
+        let colorterm = [("COLORTERM", "truecolor")];
+        assert_eq!(
+            caps("xterm-256color", &colorterm).colors,
+            ColorDepth::TrueColor
+        );
+        assert_eq!(caps("vt100", &[]).colors, ColorDepth::Mono);
+        assert_eq!(caps("dumb", &[]).colors, ColorDepth::Mono);
+    }
+
+    #[test]
+    fn no_color_wins_over_everything() {
+        let env = [("COLORTERM", "truecolor"), ("NO_COLOR", "1")];
+        assert_eq!(caps("xterm-kitty", &env).colors, ColorDepth::Mono);
+        // An empty NO_COLOR does not count
+        assert_eq!(
+            caps("xterm", &[("NO_COLOR", "")]).colors,
+            ColorDepth::Ansi16
+        );
+    }
+
+    #[test]
+    fn unicode_follows_the_locale_then_term() {
+        assert!(caps("xterm", &[]).unicode);
+        assert!(!caps("vt220", &[]).unicode);
+        assert!(!caps("xterm", &[("LANG", "C")]).unicode);
+        assert!(caps("vt220", &[("LANG", "en_US.UTF-8")]).unicode);
+        assert!(caps("xterm", &[("LANG", "fr_FR.utf8")]).unicode);
+        // LC_ALL overrides LC_CTYPE, which overrides LANG
+        let env = [("LANG", "en_US.UTF-8"), ("LC_CTYPE", "C")];
+        assert!(!caps("xterm", &env).unicode);
+        let env = [("LC_CTYPE", "C"), ("LC_ALL", "C.UTF-8")];
+        assert!(caps("xterm", &env).unicode);
+    }
+
+    #[test]
+    fn hyperlinks_are_kept_from_terminals_that_print_them() {
+        assert!(caps("xterm-256color", &[]).hyperlinks);
+        assert!(!caps("screen-256color", &[]).hyperlinks);
+        assert!(!caps("linux", &[]).hyperlinks);
+        assert!(!Capabilities::default().hyperlinks);
+    }
+
+    #[test]
+    fn unrelated_variables_are_refused() {
+        let mut caps = Capabilities::default();
+        assert!(!caps.set_env("EDITOR", "vim"));
+        assert!(!caps.set_env("lang", "C"));
+    }
+
+    #[test]
+    fn reset_and_exact_colours_pass_through() {
+        let rgb = Color::Rgb(12, 34, 56);
+        assert_eq!(ColorDepth::TrueColor.adapt(rgb), rgb);
+        assert_eq!(
+            ColorDepth::Ansi256.adapt(Color::Indexed(42)),
+            Color::Indexed(42)
+        );
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Blue), Color::Blue);
+        for depth in [ColorDepth::Mono, ColorDepth::Ansi16, ColorDepth::Ansi256] {
+            assert_eq!(depth.adapt(Color::Reset), Color::Reset);
+        }
+        assert_eq!(ColorDepth::Mono.adapt(Color::Red), Color::Reset);
+    }
+
+    #[test]
+    fn rgb_maps_to_the_nearest_palette_entry() {
+        let adapt = |depth: ColorDepth, r, g, b| depth.adapt(Color::Rgb(r, g, b));
+        // Entries of the cube and the grey ramp map to themselves
+        assert_eq!(adapt(ColorDepth::Ansi256, 255, 0, 0), Color::Indexed(196));
+        assert_eq!(adapt(ColorDepth::Ansi256, 95, 135, 175), Color::Indexed(67));
+        assert_eq!(adapt(ColorDepth::Ansi256, 0, 0, 0), Color::Indexed(16));
+        assert_eq!(
+            adapt(ColorDepth::Ansi256, 128, 128, 128),
+            Color::Indexed(244)
+        );
+        // Near greys prefer the finer grey ramp
+        assert_eq!(adapt(ColorDepth::Ansi256, 30, 32, 31), Color::Indexed(234));
+
+        assert_eq!(adapt(ColorDepth::Ansi16, 250, 10, 10), Color::LightRed);
+        assert_eq!(adapt(ColorDepth::Ansi16, 40, 42, 54), Color::Black);
+        assert_eq!(adapt(ColorDepth::Ansi16, 20, 200, 210), Color::Cyan);
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Indexed(196)),
+            Color::LightRed
+        );
+    }
+
+    #[test]
+    fn palette_indices_round_trip() {
+        for i in 16..=255 {
+            let (r, g, b) = indexed_rgb(i);
+            assert_eq!(nearest_256(r, g, b), i, "index {i}");
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+// ── OSC 52 clipboard ───────────────────────────────────────────
+//
+// The server cannot reach the visitor's clipboard, but their terminal
+// can: OSC 52 carries base64 text for the terminal to put on the system
+// clipboard. Terminals that do not support it (or have it turned off)
+// ignore the sequence.
+
+/// Escape sequence that copies `text` to the client's clipboard.
+pub fn copy_sequence(text: &str) -> Vec<u8> {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text)).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_sent_as_base64() {
+        assert_eq!(copy_sequence("me@a.dev"), b"\x1b]52;c;bWVAYS5kZXY=\x07");
+        assert_eq!(copy_sequence(""), b"\x1b]52;c;\x07");
+    }
+}
@@ -0,0 +1,490 @@
+use anyhow::bail;
+
+use crate::portfolio::{Portfolio, Tab};
+use crate::search::Target;
+use crate::theme::{self, Scheme};
+
+// ── Command palette ────────────────────────────────────────────
+//
+// `:` opens a prompt that takes shell-like commands. A command names
+// things the way they appear in the portfolio (tab titles, project
+// names, post titles) and resolves to the same places the keys lead
+// to; the app then drives its usual navigation to get there.
+
+/// Commands the palette understands, in the order `help` lists them.
+const COMMANDS: &[&str] = &["cd", "cat", "open", "theme", "whoami", "help"];
+
+/// A parsed command, with names resolved against the portfolio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Go to a tab, or to a project or post on one. A post is opened in
+    /// the reader.
+    Go {
+        tab: usize,
+        target: Target,
+    },
+    /// Switch to the colour scheme at this index of the session's list.
+    Theme(usize),
+    Whoami,
+    Help,
+}
+
+/// State of the command prompt while it is open.
+#[derive(Debug, Clone, Default)]
+pub struct CommandState {
+    pub input: String,
+    /// Names the last completion could not choose between.
+    pub candidates: Vec<String>,
+}
+
+impl CommandState {
+    /// Complete the word being typed: fully if only one name fits, else
+    /// as far as all fitting names agree, listing them as candidates.
+    pub fn complete(&mut self, portfolio: &Portfolio, schemes: &[Scheme]) {
+        let (names, typed) = match self.input.split_once(' ') {
+            Some((command, arg)) => (arguments(portfolio, schemes, command), arg.trim_start()),
+            None => (
+                COMMANDS.iter().map(|c| c.to_string()).collect(),
+                &*self.input,
+            ),
+        };
+        let fits: Vec<String> = names
+            .into_iter()
+            .filter(|name| starts_with_ignore_case(name, typed))
+            .collect();
+
+        let completed = match fits.as_slice() {
+            [] => None,
+            [only] => Some(match self.input.split_once(' ') {
+                Some((command, _)) => format!("{command} {only}"),
+                None => format!("{only} "),
+            }),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |len, name| {
+                    common_prefix_len(&first[..len], name)
+                });
+                let prefix = &first[..common];
+                // Keep what was typed when the names only agree on less
+                (prefix.len() > typed.len()).then(|| match self.input.split_once(' ') {
+                    Some((command, _)) => format!("{command} {prefix}"),
+                    None => prefix.to_string(),
+                })
+            }
+        };
+        if let Some(input) = completed {
+            self.input = input;
+        }
+        self.candidates = if fits.len() > 1 { fits } else { Vec::new() };
+    }
+}
+
+/// Parse `input` as a command, with `schemes` the themes it can name.
+pub fn parse(portfolio: &Portfolio, schemes: &[Scheme], input: &str) -> anyhow::Result<Command> {
+    let input = input.trim();
+    let (command, arg) = match input.split_once(' ') {
+        Some((command, arg)) => (command, arg.trim()),
+        None => (input, ""),
+    };
+
+    match command {
+        "cd" => match arg {
+            // Like a shell, a bare `cd` goes home
+            "" | "~" | ".." | "/" => Ok(Command::Go {
+                tab: 0,
+                target: Target::Tab,
+            }),
+            _ => match find_tab(portfolio, arg)? {
+                Some(tab) => Ok(Command::Go {
+                    tab,
+                    target: Target::Tab,
+                }),
+                None => bail!("cd: no such tab: {arg}"),
+            },
+        },
+        "cat" => {
+            if arg.is_empty() {
+                bail!("cat: missing tab or post name");
+            }
+            match first_found([tab_target(portfolio, arg), post_target(portfolio, arg)])? {
+                Some((tab, target)) => Ok(Command::Go { tab, target }),
+                None => bail!("cat: no such tab or post: {arg}"),
+            }
+        }
+        "open" => {
+            if arg.is_empty() {
+                bail!("open: missing project or post name");
+            }
+            match first_found([project_target(portfolio, arg), post_target(portfolio, arg)])? {
+                Some((tab, target)) => Ok(Command::Go { tab, target }),
+                None => bail!("open: no such project or post: {arg}"),
+            }
+        }
+        "theme" => match theme::find(schemes, arg) {
+            Some(scheme) => Ok(Command::Theme(scheme)),
+            None if arg.is_empty() => {
+                let names: Vec<&str> = schemes.iter().map(|s| s.name.as_ref()).collect();
+                bail!("theme: choose one of {}", names.join(", "))
+            }
+            None => bail!("theme: unknown theme: {arg}"),
+        },
+        "whoami" => Ok(Command::Whoami),
+        "help" => Ok(Command::Help),
+        "" => bail!("type a command, or help"),
+        _ => bail!("unknown command: {command}"),
+    }
+}
+
+/// Where a bare name leads, for deep links and exec commands: the tab
+/// with that title, else the project with that name, else the post with
+/// that title. A name that fits nothing exactly but several things by
+/// prefix is an error.
+pub fn resolve(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, Target)>> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(None);
+    }
+    first_found([
+        tab_target(portfolio, name),
+        project_target(portfolio, name),
+        post_target(portfolio, name),
+    ])
+}
+
+/// Where a name spelled out in full (ignoring case) leads, in the same
+/// order as [`resolve`] but without its prefix matching. SSH usernames
+/// go through this: a visitor's login that merely starts a title should
+/// not drop them on that tab.
+pub fn resolve_exact(portfolio: &Portfolio, name: &str) -> Option<(usize, Target)> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let is_name = |item: &str| item.eq_ignore_ascii_case(name);
+    tab_titles(portfolio)
+        .find(|(_, title)| is_name(title))
+        .map(|(tab, _)| (tab, Target::Tab))
+        .or_else(|| {
+            project_names(portfolio)
+                .find(|(_, n)| is_name(n))
+                .map(|((tab, i), _)| (tab, Target::Project(i)))
+        })
+        .or_else(|| {
+            post_titles(portfolio)
+                .find(|(_, title)| is_name(title))
+                .map(|((tab, i), _)| (tab, Target::Post(i)))
+        })
+}
+
+/// The first of `lookups` that found something. If none did, the first
+/// that failed (a name several things start with) is the error.
+fn first_found<const N: usize>(
+    lookups: [anyhow::Result<Option<(usize, Target)>>; N],
+) -> anyhow::Result<Option<(usize, Target)>> {
+    let mut error = None;
+    for lookup in lookups {
+        match lookup {
+            Ok(Some(found)) => return Ok(Some(found)),
+            Ok(None) => {}
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    }
+    match error {
+        Some(e) => Err(e),
+        None => Ok(None),
+    }
+}
+
+fn tab_target(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, Target)>> {
+    Ok(find_tab(portfolio, name)?.map(|tab| (tab, Target::Tab)))
+}
+
+fn project_target(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, Target)>> {
+    Ok(find_project(portfolio, name)?.map(|(tab, i)| (tab, Target::Project(i))))
+}
+
+fn post_target(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, Target)>> {
+    Ok(find_post(portfolio, name)?.map(|(tab, i)| (tab, Target::Post(i))))
+}
+
+/// One-line summary of the commands, for `help`.
+pub fn usage() -> String {
+    format!("commands: {}", COMMANDS.join(", "))
+}
+
+/// Names that `command` takes as its argument.
+fn arguments(portfolio: &Portfolio, schemes: &[Scheme], command: &str) -> Vec<String> {
+    let tabs = || portfolio.tabs.iter().map(|t| t.title().to_lowercase());
+    let projects = || {
+        portfolio.tabs.iter().flat_map(|t| match t {
+            Tab::Projects { categories, .. } => categories
+                .iter()
+                .flat_map(|c| &c.projects)
+                .map(|p| p.name.clone())
+                .collect(),
+            _ => Vec::new(),
+        })
+    };
+    let posts = || {
+        portfolio.tabs.iter().flat_map(|t| match t {
+            Tab::Posts { posts, .. } => posts.iter().map(|p| p.title.clone()).collect(),
+            _ => Vec::new(),
+        })
+    };
+
+    match command {
+        "cd" => tabs().collect(),
+        "cat" => tabs().chain(posts()).collect(),
+        "open" => projects().chain(posts()).collect(),
+        "theme" => schemes.iter().map(|s| s.name.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Index of the tab titled `name`, or of the only tab whose title
+/// starts with it.
+fn find_tab(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<usize>> {
+    find_by_name(tab_titles(portfolio), name)
+}
+
+/// Tab and flat index of the project named `name`.
+fn find_project(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, usize)>> {
+    find_by_name(project_names(portfolio), name)
+}
+
+/// Tab and index of the post titled `name`.
+fn find_post(portfolio: &Portfolio, name: &str) -> anyhow::Result<Option<(usize, usize)>> {
+    find_by_name(post_titles(portfolio), name)
+}
+
+fn tab_titles(portfolio: &Portfolio) -> impl Iterator<Item = (usize, &str)> {
+    portfolio
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (i, t.title()))
+}
+
+fn project_names(portfolio: &Portfolio) -> impl Iterator<Item = ((usize, usize), &str)> {
+    portfolio
+        .tabs
+        .iter()
+        .enumerate()
+        .flat_map(|(t, tab)| match tab {
+            Tab::Projects { categories, .. } => categories
+                .iter()
+                .flat_map(|c| &c.projects)
+                .enumerate()
+                .map(|(i, p)| ((t, i), p.name.as_str()))
+                .collect(),
+            _ => Vec::new(),
+        })
+}
+
+fn post_titles(portfolio: &Portfolio) -> impl Iterator<Item = ((usize, usize), &str)> {
+    portfolio
+        .tabs
+        .iter()
+        .enumerate()
+        .flat_map(|(t, tab)| match tab {
+            Tab::Posts { posts, .. } => posts
+                .iter()
+                .enumerate()
+                .map(|(i, p)| ((t, i), p.title.as_str()))
+                .collect(),
+            _ => Vec::new(),
+        })
+}
+
+/// The item named `name` (ignoring case), or else the only one whose
+/// name starts with it. Several names starting with it is an error.
+fn find_by_name<'a, T: Copy>(
+    items: impl Iterator<Item = (T, &'a str)>,
+    name: &str,
+) -> anyhow::Result<Option<T>> {
+    let mut prefixed = Vec::new();
+    for (item, item_name) in items {
+        if item_name.eq_ignore_ascii_case(name) {
+            return Ok(Some(item));
+        }
+        if starts_with_ignore_case(item_name, name) {
+            prefixed.push((item, item_name));
+        }
+    }
+    match prefixed.as_slice() {
+        [] => Ok(None),
+        [(only, _)] => Ok(Some(*only)),
+        _ => {
+            let names: Vec<&str> = prefixed.iter().map(|(_, n)| *n).collect();
+            bail!("{name} could be {}", names.join(", "))
+        }
+    }
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Length in bytes of the case-insensitive common prefix of `a` and `b`.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| !x.eq_ignore_ascii_case(y))
+        .map_or_else(|| a.len().min(b.len()), |((i, _), _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::posts::Post;
+
+    fn portfolio() -> Portfolio {
+        let mut portfolio = Portfolio::parse(
+            r#"
+            [[tabs]]
+            kind = "text"
+            title = "About"
+            body = "Hello"
+
+            [[tabs]]
+            kind = "projects"
+            title = "Projects"
+
+            [[tabs.categories]]
+            name = "Media"
+            projects = [
+                { name = "miru", description = "", tech = "Rust", url = "" },
+                { name = "mira", description = "", tech = "Go", url = "" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let post = |title: &str| Post {
+            title: title.to_string(),
+            date: "2024-01-01".to_string(),
+            tags: Vec::new(),
+            body: String::new(),
+            path: PathBuf::new(),
+        };
+        portfolio.tabs.push(Tab::Posts {
+            title: "Blog".to_string(),
+            dir: PathBuf::new(),
+            posts: vec![post("mir"), post("About this site"), post("Mirror notes")],
+        });
+        portfolio
+    }
+
+    fn go(tab: usize, target: Target) -> Command {
+        Command::Go { tab, target }
+    }
+
+    #[test]
+    fn cd_goes_to_tabs_by_prefix_and_home() {
+        let p = portfolio();
+        assert_eq!(parse(&p, &[], "cd proj").unwrap(), go(1, Target::Tab));
+        assert_eq!(parse(&p, &[], "cd").unwrap(), go(0, Target::Tab));
+        assert_eq!(parse(&p, &[], "cd ..").unwrap(), go(0, Target::Tab));
+        assert!(parse(&p, &[], "cd nowhere").is_err());
+    }
+
+    #[test]
+    fn open_prefers_an_exact_post_to_an_ambiguous_project() {
+        let p = portfolio();
+        assert_eq!(
+            parse(&p, &[], "open MIRU").unwrap(),
+            go(1, Target::Project(0))
+        );
+        assert_eq!(parse(&p, &[], "open mir").unwrap(), go(2, Target::Post(0)));
+        assert!(parse(&p, &[], "open mi").is_err());
+        assert!(parse(&p, &[], "open").is_err());
+    }
+
+    #[test]
+    fn cat_takes_tabs_before_posts() {
+        let p = portfolio();
+        assert_eq!(parse(&p, &[], "cat about").unwrap(), go(0, Target::Tab));
+        assert_eq!(
+            parse(&p, &[], "cat about this").unwrap(),
+            go(2, Target::Post(1))
+        );
+        assert!(parse(&p, &[], "cat").is_err());
+    }
+
+    #[test]
+    fn theme_names_a_scheme() {
+        let p = portfolio();
+        assert_eq!(
+            parse(&p, theme::BUNDLED, "theme Nord").unwrap(),
+            Command::Theme(5)
+        );
+        assert!(parse(&p, theme::BUNDLED, "theme solarized").is_err());
+        assert!(parse(&p, theme::BUNDLED, "theme").is_err());
+    }
+
+    #[test]
+    fn unknown_commands_are_errors() {
+        let p = portfolio();
+        assert_eq!(parse(&p, &[], " whoami ").unwrap(), Command::Whoami);
+        assert!(parse(&p, &[], "").is_err());
+        assert!(parse(&p, &[], "rm -rf").is_err());
+    }
+
+    #[test]
+    fn ambiguous_names_list_the_candidates() {
+        let p = portfolio();
+        let error = parse(&p, &[], "open mi").unwrap_err().to_string();
+        assert!(error.contains("miru") && error.contains("mira"), "{error}");
+    }
+
+    #[test]
+    fn resolve_tries_tabs_then_projects_then_posts() {
+        let p = portfolio();
+        assert_eq!(resolve(&p, "blog").unwrap(), Some((2, Target::Tab)));
+        assert_eq!(resolve(&p, "mira").unwrap(), Some((1, Target::Project(1))));
+        assert_eq!(resolve(&p, "mir").unwrap(), Some((2, Target::Post(0))));
+        assert_eq!(resolve(&p, "nothing").unwrap(), None);
+        assert_eq!(resolve(&p, "  ").unwrap(), None);
+    }
+
+    #[test]
+    fn resolve_reports_ambiguous_prefixes() {
+        let p = portfolio();
+        let error = resolve(&p, "mi").unwrap_err().to_string();
+        assert!(error.contains("miru") && error.contains("mira"), "{error}");
+    }
+
+    #[test]
+    fn usernames_must_spell_out_the_whole_name() {
+        let p = portfolio();
+        assert_eq!(resolve_exact(&p, "BLOG"), Some((2, Target::Tab)));
+        assert_eq!(resolve_exact(&p, "mira"), Some((1, Target::Project(1))));
+        assert_eq!(resolve_exact(&p, "mir"), Some((2, Target::Post(0))));
+        assert_eq!(resolve_exact(&p, "proj"), None);
+        assert_eq!(resolve_exact(&p, "mirror"), None);
+        assert_eq!(resolve_exact(&p, ""), None);
+    }
+
+    #[test]
+    fn completion_extends_to_the_common_prefix() {
+        let p = portfolio();
+        let mut state = CommandState {
+            input: "open mi".to_string(),
+            candidates: Vec::new(),
+        };
+        state.complete(&p, &[]);
+        assert_eq!(state.input, "open mir");
+        assert_eq!(state.candidates.len(), 4);
+
+        let mut state = CommandState {
+            input: "wh".to_string(),
+            candidates: Vec::new(),
+        };
+        state.complete(&p, &[]);
+        assert_eq!(state.input, "whoami ");
+        assert!(state.candidates.is_empty());
+    }
+}
@@ -1,3 +1,6 @@
+// Built-in portfolio content, used when no content file is configured.
+// See `portfolio.rs` for the owned model these tables are converted into.
+
 // ── ASCII Art Banner ───────────────────────────────────────────
 //
 // Compact "slant"-style banner for the header.
@@ -12,35 +15,32 @@ pub const BANNER: &[&str] = &[
     r"   |_|\__,_|_| |_|_| |_|_|\___|_|\_\",
 ];
 
-/// Total number of characters in the banner (for the typewriter animation).
-pub fn banner_char_count() -> usize {
-    BANNER.iter().map(|l| l.len()).sum::<usize>() + BANNER.len() // +newlines
-}
-
 // ── About ──────────────────────────────────────────────────────
 
-pub const ABOUT_LINES: &[&str] = &[
-    "Hi there \u{1F44B}, I'm Yannick!",
-    "",
-    "I'm a freelance dev based in France, obsessed with",
-    "building things and learning new stuff.",
-    "",
-    "If an idea gets stuck in my head, I'll probably end",
-    "up building it.",
-    "",
-    "My go-to stack is React Native, Expo, TypeScript,",
-    "and Next.js. I spend most of my time in mobile and",
-    "web development but I'm always curious about new",
-    "tools and technologies.",
-    "",
-    "Currently shipping Doku \u{2013} a French learning app",
-    "with graded stories and a cute cat mascot \u{1F431}",
-    "",
-    "I believe in learning by doing, always building",
-    "something, and improving along the way.",
-    "",
-    "Open to freelance opportunities \u{2013} hello@yannickh.dev",
-];
+/// Full name, for the contact card.
+pub const NAME: &str = "Yannick Herrero";
+
+/// About page, in Markdown. Reflowed to the terminal width when rendered.
+pub const ABOUT: &str = "\
+# Hi there \u{1F44B}, I'm Yannick!
+
+I'm a freelance dev based in France, obsessed with building things and \
+learning new stuff.
+
+If an idea gets stuck in my head, I'll probably end up building it.
+
+My go-to stack is **React Native**, **Expo**, **TypeScript**, and \
+**Next.js**. I spend most of my time in mobile and web development but \
+I'm always curious about new tools and technologies.
+
+Currently shipping [Doku](https://learnfrenchwithdoku.app) \u{2013} a French \
+learning app with graded stories and a cute cat mascot \u{1F431}
+
+I believe in learning by doing, always building something, and improving \
+along the way.
+
+Open to freelance opportunities \u{2013} <hello@yannickh.dev>
+";
 
 // ── Projects ───────────────────────────────────────────────────
 
@@ -174,48 +174,6 @@ pub const PROJECT_CATEGORIES: &[ProjectCategory] = &[
     },
 ];
 
-/// Flat count of all projects across categories.
-pub fn total_project_lines() -> usize {
-    let mut lines = 0;
-    for (i, cat) in PROJECT_CATEGORIES.iter().enumerate() {
-        if i > 0 {
-            lines += 1; // blank separator between categories
-        }
-        lines += 1; // category header
-        lines += 1; // blank after header
-        for (j, _) in cat.projects.iter().enumerate() {
-            if j > 0 {
-                lines += 1; // blank between projects
-            }
-            lines += 4; // name, desc, tech, url
-        }
-    }
-    lines
-}
-
-/// Total number of projects across all categories.
-pub fn total_project_count() -> usize {
-    PROJECT_CATEGORIES
-        .iter()
-        .map(|cat| cat.projects.len())
-        .sum()
-}
-
-/// Map a flat project index (0-based) to the corresponding category and project.
-/// Returns `None` if the index is out of range.
-pub fn get_project_by_flat_index(
-    idx: usize,
-) -> Option<(&'static ProjectCategory, &'static Project)> {
-    let mut remaining = idx;
-    for cat in PROJECT_CATEGORIES {
-        if remaining < cat.projects.len() {
-            return Some((cat, &cat.projects[remaining]));
-        }
-        remaining -= cat.projects.len();
-    }
-    None
-}
-
 // ── Skills ─────────────────────────────────────────────────────
 
 pub struct SkillGroup {
@@ -276,4 +234,6 @@ pub const CONTACT_ENTRIES: &[ContactEntry] = &[
     },
 ];
 
+pub const CONTACT_INTRO: &str = "Want to get in touch? Here's where you can find me:";
+
 pub const CONTACT_OUTRO: &str = "Open to freelance opportunities — feel free to reach out.";
@@ -0,0 +1,502 @@
+use anyhow::bail;
+use ratatui::text::Line;
+use serde_json::{Value, json};
+use unicode_width::UnicodeWidthStr;
+
+use crate::command;
+use crate::markdown;
+use crate::portfolio::{
+    self, ContactEntry, Portfolio, Project, ProjectCategory, Role, SkillGroup, Tab,
+};
+use crate::posts::Post;
+use crate::search::Target;
+use crate::theme::Theme;
+use crate::ui;
+use crate::vcard;
+
+// ── Non-interactive output ─────────────────────────────────────
+//
+// `ssh host projects` runs without a PTY, so instead of the TUI the
+// portfolio is printed and the channel closed, for scripts and pagers.
+// A shell without a PTY gets the whole portfolio the same way. Text is
+// laid out like the TUI panes, minus the colours; `--json` prints the
+// content model instead.
+
+/// Width plain text is wrapped to; there is no terminal to ask.
+const WIDTH: usize = 80;
+
+/// Indent of project details under the project names.
+const MAX_NAME_COLUMN: usize = 24;
+
+/// Output format of an exec command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Run an exec command: `[name] [--json]`, `vcard` or `help`. No name
+/// prints the whole portfolio; a name prints the tab, project or post it
+/// leads to, matched like deep links. `vcard` and `help` are commands
+/// only on their own, so `ssh host help desk` still looks up a name.
+pub fn run(portfolio: &Portfolio, command: &str) -> anyhow::Result<String> {
+    let mut format = Format::Text;
+    let mut words = Vec::new();
+    for word in command.split_whitespace() {
+        match word {
+            "--json" => format = Format::Json,
+            "--help" | "-h" => return Ok(usage(portfolio)),
+            _ if word.starts_with('-') => bail!("unknown option: {word}"),
+            _ => words.push(word),
+        }
+    }
+    let name = words.join(" ");
+
+    match (name.as_str(), format) {
+        ("help", _) => return Ok(usage(portfolio)),
+        ("vcard", Format::Text) => return Ok(vcard::from_portfolio(portfolio)),
+        ("vcard", Format::Json) => bail!("--json does not apply to vcard"),
+        _ => {}
+    }
+
+    let target = if name.is_empty() {
+        None
+    } else {
+        match command::resolve(portfolio, &name)? {
+            Some(target) => Some(target),
+            None => bail!("no such tab, project or post: {name}"),
+        }
+    };
+
+    Ok(match format {
+        Format::Text => text(portfolio, target),
+        Format::Json => {
+            let value = match target {
+                None => json!({ "tabs": portfolio.tabs.iter().map(tab_json).collect::<Vec<_>>() }),
+                Some((tab, target)) => target_json(&portfolio.tabs[tab], target),
+            };
+            // Serializing a `Value` cannot fail
+            serde_json::to_string_pretty(&value).unwrap_or_default() + "\n"
+        }
+    })
+}
+
+/// The whole portfolio as plain text, for shells without a PTY.
+pub fn everything(portfolio: &Portfolio) -> String {
+    text(portfolio, None)
+}
+
+/// What exec commands understand, with the names the portfolio offers.
+pub fn usage(portfolio: &Portfolio) -> String {
+    let tabs: Vec<String> = portfolio
+        .tabs
+        .iter()
+        .map(|t| t.title().to_lowercase())
+        .collect();
+    format!(
+        "usage: ssh <host> [name] [--json]\n\
+         \n\
+         Prints the whole portfolio, or the tab, project or post called\n\
+         <name>; a unique prefix is enough. Tabs: {}.\n\
+         \n\
+         \x20 --json   print JSON instead of text\n\
+         \x20 vcard    print the contact card, to save as a .vcf file\n\
+         \x20 help     show this message\n",
+        tabs.join(", ")
+    )
+}
+
+// ── Text ───────────────────────────────────────────────────────
+
+fn text(portfolio: &Portfolio, target: Option<(usize, Target)>) -> String {
+    let lines = match target {
+        None => {
+            let mut lines = Vec::new();
+            for (i, tab) in portfolio.tabs.iter().enumerate() {
+                if i > 0 {
+                    lines.push(String::new());
+                }
+                lines.extend(tab_text(tab));
+            }
+            lines
+        }
+        Some((tab, Target::Tab)) => tab_text(&portfolio.tabs[tab]),
+        Some((tab, Target::Project(i))) => match &portfolio.tabs[tab] {
+            Tab::Projects { categories, .. } => {
+                match portfolio::get_project_by_flat_index(categories, i, None) {
+                    Some((category, project)) => project_text(category, project),
+                    None => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        },
+        Some((tab, Target::Post(i))) => match &portfolio.tabs[tab] {
+            Tab::Posts { posts, .. } => posts.get(i).map(post_text).unwrap_or_default(),
+            _ => Vec::new(),
+        },
+        // Deep links only lead to tabs, projects and posts
+        Some(_) => Vec::new(),
+    };
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// A tab under its underlined title.
+fn tab_text(tab: &Tab) -> Vec<String> {
+    let title = tab.title();
+    let mut lines = vec![
+        title.to_string(),
+        "\u{2500}".repeat(title.width()),
+        String::new(),
+    ];
+
+    match tab {
+        Tab::Text { body, .. } => lines.extend(markdown_text(body, WIDTH)),
+        Tab::Projects { categories, .. } => lines.extend(projects_text(categories)),
+        Tab::Timeline { entries, .. } => {
+            for (i, role) in entries.iter().enumerate() {
+                if i > 0 {
+                    lines.push(String::new());
+                }
+                lines.extend(role_text(role));
+            }
+        }
+        Tab::Posts { posts, .. } => {
+            if posts.is_empty() {
+                lines.push("No posts yet.".to_string());
+            }
+            for post in posts {
+                lines.push(format!("{}  {}", post.date, post.title));
+            }
+        }
+        Tab::Skills { groups, .. } => lines.extend(skills_text(groups)),
+        Tab::KeyValue {
+            intro,
+            entries,
+            outro,
+            ..
+        } => lines.extend(key_value_text(intro, entries, outro)),
+    }
+    lines
+}
+
+/// Categories with their projects, details aligned under the names.
+fn projects_text(categories: &[ProjectCategory]) -> Vec<String> {
+    let column = categories
+        .iter()
+        .flat_map(|c| &c.projects)
+        .map(|p| p.name.width() + 4)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_COLUMN);
+    let indent = " ".repeat(column);
+
+    let mut lines = Vec::new();
+    for (i, category) in categories.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        lines.push(category.name.clone());
+        for project in &category.projects {
+            let mut details = markdown_text(&project.description, WIDTH - column);
+            details.push(project.tech.clone());
+            details.push(project.url.clone());
+
+            let name = format!("  {}", project.name);
+            // A name too long for the column gets a line of its own
+            if name.width() + 2 > column {
+                lines.push(name);
+                lines.extend(details.iter().map(|d| indent_line(&indent, d)));
+            } else {
+                let pad = " ".repeat(column - name.width());
+                let mut details = details.iter();
+                lines.push(format!("{name}{pad}{}", details.next().map_or("", |d| d)));
+                lines.extend(details.map(|d| indent_line(&indent, d)));
+            }
+        }
+    }
+    lines
+}
+
+/// A project laid out like the detail pane.
+fn project_text(category: &ProjectCategory, project: &Project) -> Vec<String> {
+    let mut lines = vec![project.name.clone(), category.name.clone(), String::new()];
+    lines.extend(markdown_text(&project.description, WIDTH));
+    lines.extend([
+        String::new(),
+        format!("Tech  {}", project.tech),
+        String::new(),
+        format!("URL   {}", project.url),
+    ]);
+    lines
+}
+
+/// A role laid out like the timeline detail pane.
+fn role_text(role: &Role) -> Vec<String> {
+    let mut lines = vec![role.title.clone(), role.client.clone(), role.dates()];
+    if !role.summary.is_empty() {
+        lines.push(String::new());
+        lines.extend(markdown_text(&role.summary, WIDTH));
+    }
+    if !role.highlights.is_empty() {
+        lines.push(String::new());
+        lines.push("Highlights".to_string());
+        let list: String = role.highlights.iter().map(|h| format!("- {h}\n")).collect();
+        lines.extend(markdown_text(&list, WIDTH));
+    }
+    lines
+}
+
+/// A post laid out like the reader.
+fn post_text(post: &Post) -> Vec<String> {
+    let mut meta = post.date.clone();
+    if !post.tags.is_empty() {
+        meta.push_str("  \u{00b7}  ");
+        meta.push_str(&post.tags.join(", "));
+    }
+    let mut lines = vec![post.title.clone(), meta, String::new()];
+    lines.extend(markdown_text(&post.body, WIDTH));
+    lines
+}
+
+fn skills_text(groups: &[SkillGroup]) -> Vec<String> {
+    let separator = "  \u{00b7}  ";
+    let mut lines = Vec::new();
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        lines.push(group.name.clone());
+
+        // Wrap between items rather than inside them
+        let mut line = String::new();
+        for item in &group.items {
+            if !line.is_empty() && line.width() + separator.width() + item.width() > WIDTH {
+                lines.push(std::mem::take(&mut line));
+            } else if !line.is_empty() {
+                line.push_str(separator);
+            }
+            line.push_str(item);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn key_value_text(intro: &str, entries: &[ContactEntry], outro: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !intro.is_empty() {
+        lines.extend(ui::wrap_words(intro, WIDTH));
+        lines.push(String::new());
+    }
+    for entry in entries {
+        lines.push(format!("  {:<10}{}", entry.label, entry.value));
+    }
+    if !outro.is_empty() {
+        lines.push(String::new());
+        lines.extend(ui::wrap_words(outro, WIDTH));
+    }
+    lines
+}
+
+/// Markdown rendered to `width` columns, without the styling.
+fn markdown_text(source: &str, width: usize) -> Vec<String> {
+    markdown::render(source, width, &Theme::default())
+        .iter()
+        .map(plain)
+        .collect()
+}
+
+fn plain(line: &Line) -> String {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    text.trim_end().to_string()
+}
+
+fn indent_line(indent: &str, line: &str) -> String {
+    if line.is_empty() {
+        String::new()
+    } else {
+        format!("{indent}{line}")
+    }
+}
+
+// ── JSON ───────────────────────────────────────────────────────
+
+fn target_json(tab: &Tab, target: Target) -> Value {
+    match (tab, target) {
+        (Tab::Projects { categories, .. }, Target::Project(i)) => {
+            match portfolio::get_project_by_flat_index(categories, i, None) {
+                Some((category, project)) => {
+                    let mut value = project_json(project);
+                    value["category"] = json!(category.name);
+                    value
+                }
+                None => Value::Null,
+            }
+        }
+        (Tab::Posts { posts, .. }, Target::Post(i)) => posts.get(i).map_or(Value::Null, post_json),
+        _ => tab_json(tab),
+    }
+}
+
+fn tab_json(tab: &Tab) -> Value {
+    match tab {
+        Tab::Text { title, body } => json!({ "kind": "text", "title": title, "body": body }),
+        Tab::Projects { title, categories } => json!({
+            "kind": "projects",
+            "title": title,
+            "categories": categories
+                .iter()
+                .map(|c| json!({
+                    "name": c.name,
+                    "projects": c.projects.iter().map(project_json).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        Tab::Timeline { title, entries } => json!({
+            "kind": "timeline",
+            "title": title,
+            "entries": entries
+                .iter()
+                .map(|r| json!({
+                    "title": r.title,
+                    "client": r.client,
+                    "start": r.start,
+                    "end": r.end,
+                    "summary": r.summary,
+                    "highlights": r.highlights,
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        Tab::Posts { title, posts, .. } => json!({
+            "kind": "posts",
+            "title": title,
+            "posts": posts.iter().map(post_json).collect::<Vec<_>>(),
+        }),
+        Tab::Skills { title, groups } => json!({
+            "kind": "skills",
+            "title": title,
+            "groups": groups
+                .iter()
+                .map(|g| json!({ "name": g.name, "items": g.items }))
+                .collect::<Vec<_>>(),
+        }),
+        Tab::KeyValue {
+            title,
+            intro,
+            entries,
+            outro,
+        } => json!({
+            "kind": "key_value",
+            "title": title,
+            "intro": intro,
+            "entries": entries
+                .iter()
+                .map(|e| json!({ "label": e.label, "value": e.value }))
+                .collect::<Vec<_>>(),
+            "outro": outro,
+        }),
+    }
+}
+
+fn project_json(project: &Project) -> Value {
+    json!({
+        "name": project.name,
+        "description": project.description,
+        "tech": project.tech,
+        "tags": project.tags().collect::<Vec<_>>(),
+        "url": project.url,
+    })
+}
+
+fn post_json(post: &Post) -> Value {
+    json!({
+        "title": post.title,
+        "date": post.date,
+        "tags": post.tags,
+        "body": post.body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portfolio() -> Portfolio {
+        Portfolio::parse(
+            r#"
+            [[tabs]]
+            kind = "text"
+            title = "About"
+            body = "Hello **there**"
+
+            [[tabs]]
+            kind = "projects"
+            title = "Projects"
+
+            [[tabs.categories]]
+            name = "Apps"
+            projects = [
+                { name = "miru", description = "A reader", tech = "Rust", url = "https://a.dev" },
+            ]
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn a_name_prints_what_it_leads_to() {
+        let p = portfolio();
+        assert_eq!(
+            run(&p, "about").unwrap(),
+            "About\n\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\n\nHello there\n"
+        );
+        assert!(run(&p, "miru").unwrap().contains("A reader"));
+        assert_eq!(run(&p, "").unwrap(), run(&p, "  ").unwrap());
+    }
+
+    #[test]
+    fn everything_prints_every_tab() {
+        let p = portfolio();
+        let out = everything(&p);
+        assert_eq!(out, run(&p, "").unwrap());
+        assert!(out.starts_with("About\n"));
+        assert!(out.contains("\nProjects\n"));
+        assert!(out.ends_with('\n') && !out.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn json_may_come_before_or_after_the_name() {
+        let p = portfolio();
+        let value: Value = serde_json::from_str(&run(&p, "--json miru").unwrap()).unwrap();
+        assert_eq!(value["name"], "miru");
+        assert_eq!(
+            run(&p, "miru --json").unwrap(),
+            run(&p, "--json miru").unwrap()
+        );
+
+        let value: Value = serde_json::from_str(&run(&p, "--json").unwrap()).unwrap();
+        assert_eq!(value["tabs"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn vcard_and_help_only_stand_alone() {
+        let p = portfolio();
+        assert!(run(&p, "vcard").unwrap().starts_with("BEGIN:VCARD\r\n"));
+        assert!(run(&p, "vcard --json").is_err());
+        // Part of a name, they are looked up like any other word
+        assert!(run(&p, "miru help").is_err());
+        assert!(run(&p, "vcard miru").is_err());
+    }
+
+    #[test]
+    fn help_and_bad_arguments() {
+        let p = portfolio();
+        assert!(run(&p, "help").unwrap().starts_with("usage:"));
+        assert!(run(&p, "about -h").unwrap().starts_with("usage:"));
+        assert!(run(&p, "--yaml").is_err());
+        assert!(run(&p, "nowhere").is_err());
+    }
+}
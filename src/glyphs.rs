@@ -0,0 +1,139 @@
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+// ── ASCII glyphs ───────────────────────────────────────────────
+//
+// Terminals that are not set up for UTF-8 show each byte of a multi-byte
+// character as a character of its own, which garbles the borders, arrows
+// and bullets of the UI and shifts everything after them on the line.
+// In ASCII mode a rendered frame is rewritten cell by cell before it is
+// sent: symbols with an ASCII look-alike get it, accented letters lose
+// their accents, and emoji are dropped. Every replacement is one column
+// wide, so the layout, worked out for the original text, still holds;
+// the cell after a wide character is already blank.
+
+/// ASCII stand-ins for the symbols the UI draws with, and for the
+/// punctuation common in content.
+const GLYPHS: &[(char, char)] = &[
+    // Borders, rules and the timeline
+    ('\u{2500}', '-'), // ─
+    ('\u{2502}', '|'), // │
+    ('\u{250c}', '+'), // ┌
+    ('\u{2510}', '+'), // ┐
+    ('\u{2514}', '+'), // └
+    ('\u{2518}', '+'), // ┘
+    ('\u{256d}', '+'), // ╭
+    ('\u{256e}', '+'), // ╮
+    ('\u{256f}', '+'), // ╯
+    ('\u{2570}', '+'), // ╰
+    // Arrows and markers
+    ('\u{25b8}', '>'), // ▸
+    ('\u{203a}', '>'), // ›
+    ('\u{2039}', '<'), // ‹
+    ('\u{2191}', '^'), // ↑
+    ('\u{2193}', 'v'), // ↓
+    ('\u{2190}', '<'), // ←
+    ('\u{2192}', '>'), // →
+    ('\u{25cf}', '*'), // ●
+    ('\u{25cb}', 'o'), // ○
+    ('\u{2022}', '*'), // •
+    ('\u{00b7}', '-'), // ·
+    // Cursors and the QR code
+    ('\u{2588}', '#'), // █
+    ('\u{2580}', '#'), // ▀
+    ('\u{2584}', '#'), // ▄
+    // Punctuation
+    ('\u{2026}', '.'),  // …
+    ('\u{00d7}', 'x'),  // ×
+    ('\u{2013}', '-'),  // –
+    ('\u{2014}', '-'),  // —
+    ('\u{2018}', '\''), // ‘
+    ('\u{2019}', '\''), // ’
+    ('\u{201c}', '"'),  // “
+    ('\u{201d}', '"'),  // ”
+    ('\u{00ab}', '<'),  // «
+    ('\u{00bb}', '>'),  // »
+    ('\u{00a0}', ' '),  // no-break space
+];
+
+/// Accented Latin letters, by the letter they are written on.
+const ACCENTS: &[(&str, char)] = &[
+    ("àáâãäå", 'a'),
+    ("ÀÁÂÃÄÅ", 'A'),
+    ("çć", 'c'),
+    ("ÇĆ", 'C'),
+    ("èéêë", 'e'),
+    ("ÈÉÊË", 'E'),
+    ("ìíîï", 'i'),
+    ("ÌÍÎÏ", 'I'),
+    ("ñ", 'n'),
+    ("Ñ", 'N'),
+    ("òóôõöø", 'o'),
+    ("ÒÓÔÕÖØ", 'O'),
+    ("ùúûü", 'u'),
+    ("ÙÚÛÜ", 'U'),
+    ("ýÿ", 'y'),
+    ("Ý", 'Y'),
+];
+
+/// The ASCII character drawn in place of `c`.
+fn ascii(c: char) -> char {
+    if c.is_ascii() {
+        return c;
+    }
+    GLYPHS
+        .iter()
+        .find(|(glyph, _)| *glyph == c)
+        .map(|(_, ascii)| *ascii)
+        .or_else(|| {
+            ACCENTS
+                .iter()
+                .find(|(accented, _)| accented.contains(c))
+                .map(|(_, base)| *base)
+        })
+        .unwrap_or('?')
+}
+
+/// Rewrite a rendered frame in ASCII.
+pub fn to_ascii(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let symbol = cell.symbol();
+        if symbol.is_ascii() {
+            continue;
+        }
+        // Emoji and other wide characters are dropped
+        let replacement = if symbol.width() > 1 {
+            ' '
+        } else {
+            symbol.chars().next().map_or(' ', ascii)
+        };
+        cell.set_char(replacement);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use super::*;
+
+    #[test]
+    fn symbols_and_accents_have_ascii_stand_ins() {
+        assert_eq!(ascii('a'), 'a');
+        assert_eq!(ascii('\u{256d}'), '+');
+        assert_eq!(ascii('\u{2026}'), '.');
+        assert_eq!(ascii('é'), 'e');
+        assert_eq!(ascii('Ø'), 'O');
+        assert_eq!(ascii('\u{3042}'), '?');
+    }
+
+    #[test]
+    fn frames_keep_their_layout() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 1));
+        buffer.set_string(0, 0, "\u{25b8} caf\u{e9}\u{1f980}", Style::new());
+        to_ascii(&mut buffer);
+        let row: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(row, "> cafe  ");
+    }
+}
@@ -1,35 +1,107 @@
 use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::Rect;
 use ratatui::{Terminal, TerminalOptions, Viewport};
 use russh::keys::ssh_key::PublicKey;
 use russh::server::*;
-use russh::{Channel, ChannelId, Pty};
+use russh::{Channel, ChannelId, CryptoVec, Pty};
 use tokio::sync::Mutex;
 
-use crate::app::App;
-use crate::terminal::TerminalHandle;
-use crate::ui;
+use crate::app::{self, App, Mode};
+use crate::export;
+use crate::glyphs;
+use crate::hyperlink;
+use crate::input::{self, InputDecoder};
+use crate::keymap::{Action, Keymap};
+use crate::portfolio::{Portfolio, SharedPortfolio};
+use crate::terminal::{Closer, TerminalHandle};
+use crate::theme::Scheme;
+use crate::ui::{self, HitMap};
 
 type SshTerminal = Terminal<CrosstermBackend<TerminalHandle>>;
 
-/// Per-client state: a ratatui terminal and the app model.
-type ClientState = (SshTerminal, App);
+/// Per-client state: a ratatui terminal, the app model, the decoder
+/// for the client's input and the clickable regions of the last frame.
+struct Client {
+    terminal: SshTerminal,
+    app: App,
+    input: InputDecoder,
+    hits: HitMap,
+    /// Whether the client was given a PTY, and so sees the TUI.
+    pty: bool,
+    closer: Closer,
+}
+
+impl Client {
+    fn draw(&mut self) {
+        let mut hits = HitMap::default();
+        let link = self.app.theme.link;
+        let fragments = match self.terminal.draw(|f| {
+            hits = ui::render(&self.app, f);
+            if self.app.ascii {
+                glyphs::to_ascii(f.buffer_mut());
+            }
+        }) {
+            Ok(frame) if self.app.caps.hyperlinks => {
+                hyperlink::locate(frame.buffer, &hits.links, link)
+            }
+            _ => Vec::new(),
+        };
+        let backend = self.terminal.backend_mut();
+        let _ = hyperlink::write(backend, &fragments);
+        // Outside the frame, so ratatui's diff never sees them
+        let escapes = self.app.take_escapes();
+        if !escapes.is_empty() {
+            let _ = escapes.iter().try_for_each(|e| backend.write_all(e));
+            let _ = backend.flush();
+        }
+        self.hits = hits;
+    }
+
+    /// Put the client's terminal back as the TUI found it, with mouse
+    /// reporting off and the cursor showing, then optionally close the
+    /// channel once that has been sent.
+    fn restore_terminal(&mut self, close: bool) {
+        if self.pty {
+            let _ = self.terminal.backend_mut().write_all(input::DISABLE_MOUSE);
+            let _ = self.terminal.show_cursor();
+        }
+        if close {
+            self.closer.close();
+        }
+    }
+}
 
 /// SSH server that serves the portfolio TUI to each connected client.
 #[derive(Clone)]
 pub struct AppServer {
-    clients: Arc<Mutex<HashMap<usize, ClientState>>>,
+    clients: Arc<Mutex<HashMap<usize, Client>>>,
+    portfolio: SharedPortfolio,
+    keymap: Arc<Keymap>,
+    /// Colour themes sessions can switch between.
+    schemes: Arc<[Scheme]>,
     id: usize,
+    /// Username the client authenticated as, used as a deep link.
+    user: Option<String>,
 }
 
 impl AppServer {
-    pub fn new() -> Self {
+    pub fn new(portfolio: Portfolio, keymap: Keymap, schemes: Vec<Scheme>) -> Self {
         Self {
             clients: Arc::new(Mutex::new(HashMap::new())),
+            portfolio: SharedPortfolio::new(portfolio),
+            keymap: Arc::new(keymap),
+            schemes: schemes.into(),
             id: 0,
+            user: None,
         }
     }
 
@@ -43,19 +115,119 @@ impl AppServer {
         Ok(())
     }
 
+    /// Draw a client's first frame, now that its terminal is known, and
+    /// start the intro animation.
+    async fn start_client(&self, id: usize) {
+        if let Some(client) = self.clients.lock().await.get_mut(&id) {
+            log::info!("Client {id} terminal: {}", client.app.caps);
+            client.app.start();
+        }
+        self.render_client(id).await;
+        self.spawn_intro_animation(id);
+    }
+
     /// Re-render the TUI for a specific client.
     async fn render_client(&self, id: usize) {
         let mut clients = self.clients.lock().await;
-        if let Some((terminal, app)) = clients.get_mut(&id) {
-            let _ = terminal.draw(|f| {
-                ui::render(app, f);
-            });
+        if let Some(client) = clients.get_mut(&id) {
+            client.app.sync_portfolio(&self.portfolio.current());
+            client.draw();
         }
     }
 
+    /// Poll the content file (and any post files) for changes and
+    /// hot-swap the portfolio.
+    ///
+    /// A file that fails to load is logged and ignored, leaving the
+    /// previous content live. Connected clients are re-rendered right
+    /// after a successful reload.
+    pub fn spawn_content_watcher(&self, path: PathBuf) {
+        let clients = self.clients.clone();
+        let portfolio = self.portfolio.clone();
+        tokio::spawn(async move {
+            let poll = Duration::from_secs(2);
+            let stamps = |portfolio: &SharedPortfolio| {
+                let mut files = vec![path.clone()];
+                files.extend(portfolio.current().watched_files());
+                files.iter().map(|f| file_stamp(f)).collect::<Vec<_>>()
+            };
+            let mut last_seen = stamps(&portfolio);
+
+            loop {
+                tokio::time::sleep(poll).await;
+
+                let stamp = stamps(&portfolio);
+                if stamp == last_seen {
+                    continue;
+                }
+
+                match Portfolio::load(&path) {
+                    Ok(new) => {
+                        portfolio.replace(new);
+                        // Posts may have been added or removed
+                        last_seen = stamps(&portfolio);
+                        log::info!("Reloaded portfolio content from {}", path.display());
+                    }
+                    Err(e) => {
+                        log::error!("Keeping previous content, reload failed: {e:#}");
+                        last_seen = stamp;
+                        continue;
+                    }
+                }
+
+                let latest = portfolio.current();
+                let mut guard = clients.lock().await;
+                for client in guard.values_mut() {
+                    client.app.sync_portfolio(&latest);
+                    client.draw();
+                }
+            }
+        });
+    }
+
+    /// Take a lone Esc as the Esc key if the rest of an escape sequence
+    /// does not arrive within [`input::ESC_TIMEOUT`].
+    fn spawn_esc_timeout(&self, id: usize) {
+        let clients = self.clients.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(input::ESC_TIMEOUT).await;
+
+            let mut guard = clients.lock().await;
+            let Some(client) = guard.get_mut(&id) else {
+                return;
+            };
+            // Newer input was either decoded already or has its own timeout
+            if !client.input.is_stale() {
+                return;
+            }
+            for event in client.input.flush() {
+                handle_event(client, event);
+            }
+            client.draw();
+        });
+    }
+
+    /// Take down a client's toast once it has been up for
+    /// [`app::TOAST_DURATION`].
+    fn spawn_toast_timeout(&self, id: usize) {
+        let clients = self.clients.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(app::TOAST_DURATION).await;
+
+            let mut guard = clients.lock().await;
+            // A newer toast has its own timeout
+            if let Some(client) = guard.get_mut(&id)
+                && client.app.expire_toast()
+            {
+                client.draw();
+            }
+        });
+    }
+
     /// Spawn the intro animation ticker for a client.
     fn spawn_intro_animation(&self, id: usize) {
         let clients = self.clients.clone();
+        let portfolio = self.portfolio.clone();
         tokio::spawn(async move {
             // Characters to reveal per tick — controls typing speed
             let chars_per_tick: usize = 4;
@@ -65,14 +237,13 @@ impl AppServer {
                 tokio::time::sleep(std::time::Duration::from_millis(tick_ms)).await;
 
                 let mut guard = clients.lock().await;
-                let should_stop = if let Some((terminal, app)) = guard.get_mut(&id) {
-                    let changed = app.advance_intro(chars_per_tick);
+                let should_stop = if let Some(client) = guard.get_mut(&id) {
+                    client.app.sync_portfolio(&portfolio.current());
+                    let changed = client.app.advance_intro(chars_per_tick);
                     if changed {
-                        let _ = terminal.draw(|f| {
-                            ui::render(app, f);
-                        });
+                        client.draw();
                     }
-                    app.intro_done()
+                    client.app.intro_done()
                 } else {
                     // Client disconnected
                     true
@@ -104,17 +275,20 @@ impl Server for AppServer {
 impl Handler for AppServer {
     type Error = anyhow::Error;
 
-    /// Accept all connections without authentication.
-    async fn auth_none(&mut self, _user: &str) -> Result<Auth, Self::Error> {
+    /// Accept all connections without authentication. The username is
+    /// kept as a deep link (`ssh projects@host`).
+    async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+        self.user = Some(user.to_string());
         Ok(Auth::Accept)
     }
 
     /// Also accept any public key (fallback for clients that try pubkey first).
     async fn auth_publickey(
         &mut self,
-        _user: &str,
+        user: &str,
         _key: &PublicKey,
     ) -> Result<Auth, Self::Error> {
+        self.user = Some(user.to_string());
         Ok(Auth::Accept)
     }
 
@@ -127,24 +301,46 @@ impl Handler for AppServer {
         let terminal_handle =
             TerminalHandle::start(session.handle(), channel.id()).await;
 
+        let closer = terminal_handle.closer();
         let backend = CrosstermBackend::new(terminal_handle);
         let options = TerminalOptions {
             viewport: Viewport::Fixed(Rect::default()),
         };
         let terminal = Terminal::with_options(backend, options)?;
-        let app = App::new();
+        let mut app = App::new(
+            self.portfolio.current(),
+            self.keymap.clone(),
+            self.schemes.clone(),
+        );
+        if let Some(user) = &self.user {
+            app.user = user.clone();
+            // `ssh projects@host` opens the Projects tab; a username that
+            // names nothing is just the visitor's login
+            if app.open_user_link(user) {
+                log::info!("Client {} deep linked to {user:?} by username", self.id);
+            }
+        }
 
-        self.clients.lock().await.insert(self.id, (terminal, app));
+        let client = Client {
+            terminal,
+            app,
+            input: InputDecoder::default(),
+            hits: HitMap::default(),
+            pty: false,
+            closer,
+        };
+        self.clients.lock().await.insert(self.id, client);
 
         Ok(true)
     }
 
-    /// Client requests a PTY — capture the terminal dimensions, do the
-    /// initial render, and start the intro animation.
+    /// Client requests a PTY — capture the terminal dimensions and type.
+    /// The first frame waits for the shell or exec request, by which
+    /// time the client has sent its environment too.
     async fn pty_request(
         &mut self,
         channel: ChannelId,
-        _term: &str,
+        term: &str,
         col_width: u32,
         row_height: u32,
         _pix_width: u32,
@@ -161,18 +357,115 @@ impl Handler for AppServer {
 
         {
             let mut clients = self.clients.lock().await;
-            if let Some((terminal, _)) = clients.get_mut(&self.id) {
+            if let Some(Client {
+                terminal, app, pty, ..
+            }) = clients.get_mut(&self.id)
+            {
                 terminal.resize(rect)?;
+                app.resize(rect.width, rect.height);
+                app.set_term(term);
+                *pty = true;
+
+                // Report clicks and the wheel for the rest of the session
+                let backend = terminal.backend_mut();
+                backend.write_all(input::ENABLE_MOUSE)?;
+                backend.flush()?;
             }
         }
 
-        // Initial render (will show the intro animation first frame)
-        self.render_client(self.id).await;
+        session.channel_success(channel)?;
+        Ok(())
+    }
 
-        // Start the intro animation ticker
-        self.spawn_intro_animation(self.id);
+    /// Client runs a command. With a PTY (`ssh host -t miru`) the
+    /// command is a deep link into the TUI; without one (`ssh host
+    /// projects --json`) its output is printed and the channel closed.
+    async fn exec_request(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let command = String::from_utf8_lossy(data);
+        let pty = {
+            let mut clients = self.clients.lock().await;
+            match clients.get_mut(&self.id) {
+                Some(client) if client.pty => {
+                    if !client.app.open_link(&command) {
+                        log::info!("Client {} asked for unknown {command:?}", self.id);
+                    }
+                    true
+                }
+                // No TUI to drive: the client only needs the output
+                _ => {
+                    clients.remove(&self.id);
+                    false
+                }
+            }
+        };
+
+        if pty {
+            self.start_client(self.id).await;
+            session.channel_success(channel)?;
+            return Ok(());
+        }
+
+        log::info!("Client {} ran {command:?}", self.id);
+        session.channel_success(channel)?;
+        let output = export::run(&self.portfolio.current(), &command)
+            .map_err(|e| format!("{e:#}\nTry `help` for usage.\n"));
+        print_and_close(session, channel, output)
+    }
+
+    /// Client asks for a shell. With a PTY the TUI is already up; without
+    /// one (`ssh -T host`, or from a script) nothing could drive it, so
+    /// the whole portfolio is printed as plain text instead.
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let pty = {
+            let mut clients = self.clients.lock().await;
+            let pty = clients.get(&self.id).is_some_and(|c| c.pty);
+            if !pty {
+                clients.remove(&self.id);
+            }
+            pty
+        };
 
         session.channel_success(channel)?;
+        if pty {
+            self.start_client(self.id).await;
+            return Ok(());
+        }
+        log::info!("Client {} has no PTY, printing plain text", self.id);
+        let output = export::everything(&self.portfolio.current());
+        print_and_close(session, channel, Ok(output))
+    }
+
+    /// Client passes an environment variable. Those describing the
+    /// terminal (COLORTERM, LANG, ...) refine its capabilities before the
+    /// first frame; the rest are refused.
+    async fn env_request(
+        &mut self,
+        channel: ChannelId,
+        variable_name: &str,
+        variable_value: &str,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let accepted = {
+            let mut clients = self.clients.lock().await;
+            clients
+                .get_mut(&self.id)
+                .is_some_and(|client| client.app.set_env(variable_name, variable_value))
+        };
+
+        if accepted {
+            session.channel_success(channel)?;
+        } else {
+            session.channel_failure(channel)?;
+        }
         Ok(())
     }
 
@@ -195,8 +488,9 @@ impl Handler for AppServer {
 
         {
             let mut clients = self.clients.lock().await;
-            if let Some((terminal, _)) = clients.get_mut(&self.id) {
+            if let Some(Client { terminal, app, .. }) = clients.get_mut(&self.id) {
                 terminal.resize(rect)?;
+                app.resize(rect.width, rect.height);
             }
         }
 
@@ -204,132 +498,285 @@ impl Handler for AppServer {
         Ok(())
     }
 
+    /// Client will send no more input, so nothing can drive the TUI.
+    async fn channel_eof(
+        &mut self,
+        _channel: ChannelId,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        end_client(&self.clients, self.id, true).await;
+        Ok(())
+    }
+
+    /// Client closed the channel.
+    async fn channel_close(
+        &mut self,
+        _channel: ChannelId,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        end_client(&self.clients, self.id, false).await;
+        Ok(())
+    }
+
     /// Client sent data (keypresses as raw bytes).
     async fn data(
         &mut self,
-        channel: ChannelId,
+        _channel: ChannelId,
         data: &[u8],
-        session: &mut Session,
+        _session: &mut Session,
     ) -> Result<(), Self::Error> {
         let mut should_quit = false;
         let mut needs_render = false;
+        let mut wait_for_esc = false;
+        let mut toast = false;
 
         {
             let mut clients = self.clients.lock().await;
-            if let Some((terminal, app)) = clients.get_mut(&self.id) {
-                // If intro is still playing, any keypress skips it
-                if !app.intro_done() {
-                    app.skip_intro();
-                    let _ = terminal.draw(|f| {
-                        ui::render(app, f);
-                    });
-                    // Don't process the keypress further
-                } else {
-                    // Get viewport height for scroll calculations
-                    let viewport_h = terminal.size().map(|s| s.height).unwrap_or(24);
-                    // Estimate content area height (total - header - tabs - footer - borders/padding)
-                    let content_h = viewport_h.saturating_sub(14) as usize;
-
-                    let on_projects_tab = app.tab == crate::app::Tab::Projects;
-
-                    match data {
-                        // 'q' or Ctrl-C — quit
-                        b"q" | b"Q" | b"\x03" => {
-                            app.quit();
-                            should_quit = true;
-                        }
-                        // Right arrow, Tab, or vim 'l' — next tab
-                        b"\x1b[C" | b"\t" | b"l" => {
-                            app.next_tab();
-                            needs_render = true;
-                        }
-                        // Left arrow, Shift-Tab, or vim 'h' — prev tab
-                        b"\x1b[D" | b"\x1b[Z" | b"h" => {
-                            app.prev_tab();
-                            needs_render = true;
-                        }
-                        // Up arrow or vim 'k' — scroll up / select previous project
-                        b"\x1b[A" | b"k" => {
-                            if on_projects_tab {
-                                app.select_prev_project();
-                            } else {
-                                app.scroll_up();
-                            }
-                            needs_render = true;
-                        }
-                        // Down arrow or vim 'j' — scroll down / select next project
-                        b"\x1b[B" | b"j" => {
-                            if on_projects_tab {
-                                app.select_next_project();
-                            } else {
-                                let total = app.content_line_count();
-                                app.scroll_down(total, content_h);
-                            }
-                            needs_render = true;
-                        }
-                        // vim 'g' — scroll to top / select first project
-                        b"g" => {
-                            if on_projects_tab {
-                                app.select_first_project();
-                            } else {
-                                app.scroll_offset = 0;
-                            }
-                            needs_render = true;
-                        }
-                        // vim 'G' — scroll to bottom / select last project
-                        b"G" => {
-                            if on_projects_tab {
-                                app.select_last_project();
-                            } else {
-                                let total = app.content_line_count();
-                                if total > content_h {
-                                    app.scroll_offset = total - content_h;
-                                }
-                            }
-                            needs_render = true;
-                        }
-                        // '1' .. '4' — jump to tab directly
-                        b"1" => {
-                            app.go_to_tab(0);
-                            needs_render = true;
-                        }
-                        b"2" => {
-                            app.go_to_tab(1);
-                            needs_render = true;
-                        }
-                        b"3" => {
-                            app.go_to_tab(2);
-                            needs_render = true;
-                        }
-                        b"4" => {
-                            app.go_to_tab(3);
-                            needs_render = true;
-                        }
-                        _ => {
-                            // Ignore unknown input
-                        }
-                    }
+            if let Some(client) = clients.get_mut(&self.id) {
+                client.app.sync_portfolio(&self.portfolio.current());
+
+                let events = client.input.feed(data);
+                needs_render = !events.is_empty();
+                let had_toast = client.app.toast.as_ref().map(|t| t.shown);
+                for event in events {
+                    handle_event(client, event);
                 }
+                toast = client.app.toast.as_ref().map(|t| t.shown) != had_toast;
+                should_quit = client.app.should_quit;
+                wait_for_esc = client.input.has_pending();
             }
         }
 
         if should_quit {
-            self.clients.lock().await.remove(&self.id);
-            session.close(channel)?;
-        } else if needs_render {
-            self.render_client(self.id).await;
+            end_client(&self.clients, self.id, true).await;
+        } else {
+            if needs_render {
+                self.render_client(self.id).await;
+            }
+            if wait_for_esc {
+                self.spawn_esc_timeout(self.id);
+            }
+            if toast {
+                self.spawn_toast_timeout(self.id);
+            }
         }
 
         Ok(())
     }
 }
 
+/// Forget client `id` and restore its terminal. Every way out of the
+/// TUI ends here: quitting, the client closing its end, or the
+/// connection timing out.
+async fn end_client(clients: &Mutex<HashMap<usize, Client>>, id: usize, close: bool) {
+    if let Some(mut client) = clients.lock().await.remove(&id) {
+        client.restore_terminal(close);
+    }
+}
+
+/// Send a command's output (or its error, on stderr), then exit with
+/// the matching status and close the channel.
+fn print_and_close(
+    session: &mut Session,
+    channel: ChannelId,
+    output: Result<String, String>,
+) -> anyhow::Result<()> {
+    let status = match output {
+        Ok(output) => {
+            session.data(channel, CryptoVec::from_slice(output.as_bytes()))?;
+            0
+        }
+        Err(message) => {
+            session.extended_data(channel, 1, CryptoVec::from_slice(message.as_bytes()))?;
+            1
+        }
+    };
+    session.exit_status_request(channel, status)?;
+    session.eof(channel)?;
+    session.close(channel)?;
+    Ok(())
+}
+
+// ── Key and mouse bindings ─────────────────────────────────────
+
+fn handle_event(client: &mut Client, event: Event) {
+    match event {
+        Event::Key(key) => handle_key(&mut client.app, key),
+        Event::Mouse(mouse) => handle_mouse(&mut client.app, &client.hits, mouse),
+        _ => {}
+    }
+}
+
+/// Apply a mouse event, hit-testing it against the last frame.
+fn handle_mouse(app: &mut App, hits: &HitMap, mouse: MouseEvent) {
+    let (column, row) = (mouse.column, mouse.row);
+    let wheel_steps = 3;
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) if !app.intro_done() => app.skip_intro(),
+        // Overlays only take the keyboard
+        _ if !matches!(app.mode, Mode::Normal) => {}
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(tab) = hits.tab_at(column, row) {
+                app.go_to_tab(tab);
+            } else if let Some(item) = hits.item_at(column, row) {
+                app.click_item(item);
+            }
+        }
+        MouseEventKind::ScrollUp if hits.in_content(column, row) => {
+            for _ in 0..wheel_steps {
+                if app.has_selection() {
+                    app.select_prev();
+                } else {
+                    app.scroll_up();
+                }
+            }
+        }
+        MouseEventKind::ScrollDown if hits.in_content(column, row) => {
+            let (_, content_h) = app.content_viewport();
+            let total = app.content_line_count();
+            for _ in 0..wheel_steps {
+                if app.has_selection() {
+                    app.select_next();
+                } else {
+                    app.scroll_down(total, content_h);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Apply a single key to the app, routing it to whichever mode is active.
+fn handle_key(app: &mut App, key: KeyEvent) {
+    // If intro is still playing, any keypress skips it
+    if !app.intro_done() {
+        app.skip_intro();
+        return;
+    }
+    // Command output stays up until the next key
+    app.status = None;
+
+    if app.searching().is_some() {
+        // The search prompt takes all input until closed
+        search_key(app, key);
+    } else if app.picking_tag().is_some() {
+        tag_picker_key(app, key);
+    } else if app.picking_theme().is_some() {
+        theme_picker_key(app, key);
+    } else if app.showing_help().is_some() {
+        help_key(app, key);
+    } else if app.command_prompt().is_some() {
+        command_key(app, key);
+    } else if app.showing_contact_card() {
+        contact_card_key(app, key);
+    } else {
+        normal_key(app, key);
+    }
+}
+
+/// Navigation bindings, looked up in the keymap.
+fn normal_key(app: &mut App, key: KeyEvent) {
+    if let Some(action) = app.keymap.action_for(&key) {
+        app.apply(action);
+    }
+}
+
+/// Keys for the search prompt.
+fn search_key(app: &mut App, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        // Esc or Ctrl-C — close without jumping
+        KeyCode::Esc => app.cancel_search(),
+        KeyCode::Char('c') if ctrl => app.cancel_search(),
+        // Enter — jump to the highlighted result
+        KeyCode::Enter => app.confirm_search(),
+        // Up arrow / Ctrl-P, Down arrow / Ctrl-N — move through results
+        KeyCode::Up => app.search_select_prev(),
+        KeyCode::Char('p') if ctrl => app.search_select_prev(),
+        KeyCode::Down => app.search_select_next(),
+        KeyCode::Char('n') if ctrl => app.search_select_next(),
+        KeyCode::Backspace => app.search_backspace(),
+        // Ctrl-U — clear the query
+        KeyCode::Char('u') if ctrl => app.search_clear(),
+        KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+            app.search_push(c)
+        }
+        _ => {}
+    }
+}
+
+/// Keys for the command palette.
+fn command_key(app: &mut App, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => app.cancel_command(),
+        KeyCode::Char('c') if ctrl => app.cancel_command(),
+        KeyCode::Enter => app.run_command(),
+        KeyCode::Tab => app.complete_command(),
+        KeyCode::Backspace => app.command_backspace(),
+        // Ctrl-U — clear the prompt
+        KeyCode::Char('u') if ctrl => app.command_clear(),
+        KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+            app.command_push(c)
+        }
+        _ => {}
+    }
+}
+
+/// Keys for the tag picker, which reuses the navigation bindings.
+fn tag_picker_key(app: &mut App, key: KeyEvent) {
+    match app.keymap.action_for(&key) {
+        Some(Action::Up) => app.tag_picker_prev(),
+        Some(Action::Down) => app.tag_picker_next(),
+        Some(Action::Open) => app.confirm_tag_picker(),
+        Some(Action::Back | Action::Filter | Action::Quit) => app.close_tag_picker(),
+        _ => {}
+    }
+}
+
+/// Keys for the theme picker, which reuses the navigation bindings.
+fn theme_picker_key(app: &mut App, key: KeyEvent) {
+    match app.keymap.action_for(&key) {
+        Some(Action::Up) => app.theme_picker_prev(),
+        Some(Action::Down) => app.theme_picker_next(),
+        Some(Action::Open) => app.confirm_theme_picker(),
+        Some(Action::Back | Action::PickTheme | Action::Quit) => app.close_theme_picker(),
+        _ => {}
+    }
+}
+
+/// Keys for the help overlay.
+fn help_key(app: &mut App, key: KeyEvent) {
+    match app.keymap.action_for(&key) {
+        Some(Action::Up) => app.help_scroll_up(),
+        Some(Action::Down) => app.help_scroll_down(),
+        Some(Action::Help | Action::Back | Action::Quit) => app.close_help(),
+        _ => {}
+    }
+}
+
+/// Keys for the contact card overlay.
+fn contact_card_key(app: &mut App, key: KeyEvent) {
+    if let Some(Action::ContactCard | Action::Back | Action::Open | Action::Quit) =
+        app.keymap.action_for(&key)
+    {
+        app.close_contact_card();
+    }
+}
+
+/// Modification time and size of a file, used to detect edits.
+fn file_stamp(path: &std::path::Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
 impl Drop for AppServer {
     fn drop(&mut self) {
         let id = self.id;
         let clients = self.clients.clone();
         tokio::spawn(async move {
-            clients.lock().await.remove(&id);
+            end_client(&clients, id, false).await;
         });
     }
 }
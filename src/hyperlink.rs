@@ -0,0 +1,293 @@
+use std::io::{self, Write};
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Modifier, Style};
+
+// ── OSC 8 hyperlinks ───────────────────────────────────────────
+//
+// ratatui has no notion of hyperlinks, and escape sequences inside span
+// text would throw off its width calculations. Instead, renderers
+// record the links they draw, and once a frame is on screen the links
+// are found in the frame's buffer (as runs of cells in the link style
+// that spell them out) and printed again, wrapped in OSC 8 sequences.
+// Wrapped and scrolled links are handled for free, and a popup drawn
+// over a link hides it from the scan.
+
+/// A link drawn in a frame: its text, and where it leads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    pub target: String,
+}
+
+impl Link {
+    /// A link for `text` if it is a URL or an email address. Emails lead
+    /// to `mailto:`; bare domains like `github.com/user` to `https://`.
+    pub fn detect(text: &str) -> Option<Self> {
+        let text = text.trim();
+        // Control characters could end the escape sequence early
+        if text.is_empty() || text.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            return None;
+        }
+        let target = if text.starts_with("https://")
+            || text.starts_with("http://")
+            || text.starts_with("mailto:")
+        {
+            text.to_string()
+        } else if let Some((user, domain)) = text.split_once('@') {
+            if user.is_empty() || !domain.contains('.') {
+                return None;
+            }
+            format!("mailto:{text}")
+        } else {
+            let host = text.split('/').next().unwrap_or(text);
+            if !host.contains('.') || host.starts_with('.') || host.ends_with('.') {
+                return None;
+            }
+            format!("https://{text}")
+        };
+        Some(Self {
+            text: text.to_string(),
+            target,
+        })
+    }
+}
+
+/// A piece of a link as laid out on screen, with the style it was drawn
+/// in.
+#[derive(Debug, Clone)]
+pub struct Fragment {
+    x: u16,
+    y: u16,
+    text: String,
+    style: Style,
+    target: String,
+}
+
+/// Whether a client terminal, going by its `TERM`, can be sent OSC 8.
+///
+/// Terminals that do not know the sequence are meant to ignore it, but
+/// the Linux console, old hardware terminals and GNU screen print parts
+/// of it as text, so those get the plain underlined text instead.
+pub fn supported(term: &str) -> bool {
+    let term = term.to_ascii_lowercase();
+    !(term.is_empty()
+        || term == "dumb"
+        || term == "linux"
+        || term == "ansi"
+        || term.starts_with("vt")
+        || term.starts_with("cons")
+        || term.starts_with("screen"))
+}
+
+/// Find where `links`, drawn in the `link` style, ended up in a
+/// rendered frame.
+///
+/// A run of link cells is only taken for a link if it spells out the
+/// link's whole text, or, for a link wrapped over several rows, if it
+/// and the runs starting the rows below do. Anything else in the link
+/// style, like a link half hidden by a popup, is left as it is.
+pub fn locate(buffer: &Buffer, links: &[Link], link: Style) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+    if links.is_empty() {
+        return fragments;
+    }
+
+    let runs = runs(buffer, link);
+    let mut i = 0;
+    while i < runs.len() {
+        let Some((found, n)) = links
+            .iter()
+            .find_map(|l| spelled_by(&runs[i..], &l.text).map(|n| (l, n)))
+        else {
+            i += 1;
+            continue;
+        };
+        fragments.extend(runs[i..i + n].iter().map(|run| Fragment {
+            x: run.x,
+            y: run.y,
+            text: run.text.clone(),
+            style: run.style,
+            target: found.target.clone(),
+        }));
+        i += n;
+    }
+    fragments
+}
+
+/// A row's worth of consecutive cells in the link style.
+struct Run {
+    x: u16,
+    y: u16,
+    text: String,
+    style: Style,
+}
+
+/// Every run of link cells in `buffer`, top to bottom.
+fn runs(buffer: &Buffer, link: Style) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            if !is_link_cell(buffer, x, y, link) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            let style = buffer[(x, y)].style();
+            let mut text = String::new();
+            while x < area.right() && is_link_cell(buffer, x, y, link) {
+                text.push_str(buffer[(x, y)].symbol());
+                x += 1;
+            }
+            runs.push(Run {
+                x: start,
+                y,
+                text,
+                style,
+            });
+        }
+    }
+    runs
+}
+
+/// How many of `runs`, from the first, spell out `text`: one, or one per
+/// row for a wrapped link.
+fn spelled_by(runs: &[Run], text: &str) -> Option<usize> {
+    let mut rest = text;
+    let top = runs.first()?.y;
+    for (n, run) in runs.iter().enumerate() {
+        if usize::from(run.y - top) != n || run.text.is_empty() {
+            return None;
+        }
+        rest = rest.strip_prefix(run.text.as_str())?;
+        if rest.is_empty() {
+            return Some(n + 1);
+        }
+    }
+    None
+}
+
+/// Print `fragments` over the frame already on screen, as hyperlinks in
+/// the colours and modifiers their cells were drawn with.
+pub fn write(w: &mut impl Write, fragments: &[Fragment]) -> io::Result<()> {
+    if fragments.is_empty() {
+        return Ok(());
+    }
+    for fragment in fragments {
+        let style = fragment.style;
+        queue!(
+            w,
+            MoveTo(fragment.x, fragment.y),
+            SetForegroundColor(style.fg.unwrap_or_default().into()),
+            SetBackgroundColor(style.bg.unwrap_or_default().into()),
+        )?;
+        for attribute in attributes(style.add_modifier) {
+            queue!(w, SetAttribute(attribute))?;
+        }
+        queue!(
+            w,
+            Print(format!(
+                "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\",
+                fragment.target, fragment.text
+            )),
+            SetAttribute(Attribute::Reset),
+        )?;
+    }
+    w.flush()
+}
+
+/// The terminal attributes that turn on `modifier`.
+fn attributes(modifier: Modifier) -> impl Iterator<Item = Attribute> {
+    [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+        (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::HIDDEN, Attribute::Hidden),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ]
+    .into_iter()
+    .filter(move |(m, _)| modifier.contains(*m))
+    .map(|(_, attribute)| attribute)
+}
+
+fn is_link_cell(buffer: &Buffer, x: u16, y: u16, link: Style) -> bool {
+    let cell = &buffer[(x, y)];
+    cell.fg == link.fg.unwrap_or_default() && cell.modifier.contains(link.add_modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Modifier, Style};
+
+    use super::*;
+
+    fn target(text: &str) -> Option<String> {
+        Link::detect(text).map(|link| link.target)
+    }
+
+    #[test]
+    fn urls_emails_and_domains_are_links() {
+        assert_eq!(
+            target(" https://a.dev/x ").as_deref(),
+            Some("https://a.dev/x")
+        );
+        assert_eq!(target("me@a.dev").as_deref(), Some("mailto:me@a.dev"));
+        assert_eq!(
+            target("github.com/me").as_deref(),
+            Some("https://github.com/me")
+        );
+        assert_eq!(target("@a.dev"), None);
+        assert_eq!(target("me@localhost"), None);
+        assert_eq!(target("Berlin"), None);
+        assert_eq!(target(".dev"), None);
+        assert_eq!(target("a.dev\x1b"), None);
+        assert_eq!(target("two words.dev"), None);
+    }
+
+    #[test]
+    fn limited_terminals_get_plain_text() {
+        assert!(supported("xterm-256color"));
+        assert!(supported("tmux-256color"));
+        assert!(!supported(""));
+        assert!(!supported("linux"));
+        assert!(!supported("VT100"));
+        assert!(!supported("screen.xterm-256color"));
+    }
+
+    #[test]
+    fn links_are_found_in_the_buffer_even_when_wrapped() {
+        let style = Style::new()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::UNDERLINED);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 3));
+        buffer.set_string(0, 0, "see ", Style::new());
+        buffer.set_string(4, 0, "https://", style);
+        buffer.set_string(0, 1, "a.dev/x", style);
+        buffer.set_string(0, 2, "a.dev", Style::new());
+        let link = Link::detect("https://a.dev/x").unwrap();
+
+        let fragments = locate(&buffer, &[link], style);
+        let found: Vec<_> = fragments
+            .iter()
+            .map(|f| (f.x, f.y, f.text.as_str(), f.target.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (4, 0, "https://", "https://a.dev/x"),
+                (0, 1, "a.dev/x", "https://a.dev/x"),
+            ]
+        );
+        assert!(locate(&buffer, &[], style).is_empty());
+    }
+}
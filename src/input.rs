@@ -0,0 +1,462 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+// ── Input decoding ─────────────────────────────────────────────
+//
+// SSH delivers keystrokes as raw bytes in arbitrary chunks: one packet
+// may hold several keys (fast typing, pastes), and an escape sequence
+// may be split across packets. The decoder buffers incomplete input
+// between calls and turns the byte stream into key and mouse events.
+//
+// A lone Esc byte is ambiguous: it is either the Esc key or the start
+// of a sequence whose rest has not arrived yet. It is held back until
+// more input arrives or `ESC_TIMEOUT` passes, whichever comes first.
+
+/// How long a lone Esc waits for the rest of an escape sequence before
+/// it is taken as the Esc key.
+pub const ESC_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Turn on mouse reporting: button presses, releases and the wheel
+/// (1000), in SGR encoding (1006), which has no coordinate limit.
+pub const ENABLE_MOUSE: &[u8] = b"\x1b[?1000h\x1b[?1006h";
+
+/// Undo [`ENABLE_MOUSE`], leaving the client's terminal as we found it.
+pub const DISABLE_MOUSE: &[u8] = b"\x1b[?1006l\x1b[?1000l";
+
+const ESC: u8 = 0x1b;
+
+/// Per-session decoder from raw input bytes to key and mouse events.
+#[derive(Debug, Default)]
+pub struct InputDecoder {
+    /// Bytes of an incomplete escape sequence or UTF-8 character.
+    pending: Vec<u8>,
+    /// When input last arrived.
+    last_input: Option<Instant>,
+}
+
+/// Result of decoding at the start of the buffer.
+enum Decoded {
+    /// A key, and the number of bytes it took.
+    Key(KeyEvent, usize),
+    /// A mouse report, and the number of bytes it took.
+    Mouse(MouseEvent, usize),
+    /// Bytes that do not map to a key (unknown sequences, stray bytes).
+    Skip(usize),
+    /// The buffer ends in the middle of a key.
+    Incomplete,
+}
+
+impl InputDecoder {
+    /// Decode `data`, along with anything held back from earlier calls.
+    pub fn feed(&mut self, data: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(data);
+        self.last_input = Some(Instant::now());
+        self.decode(false)
+    }
+
+    /// Whether input is being held back, waiting for more bytes. Callers
+    /// should call [`InputDecoder::flush`] once it [is stale](Self::is_stale).
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Whether held-back input has waited [`ESC_TIMEOUT`] without any
+    /// more input arriving.
+    pub fn is_stale(&self) -> bool {
+        self.has_pending() && self.last_input.is_some_and(|t| t.elapsed() >= ESC_TIMEOUT)
+    }
+
+    /// Give up waiting: decode whatever is held back as it stands, so a
+    /// lone Esc becomes the Esc key.
+    pub fn flush(&mut self) -> Vec<Event> {
+        self.decode(true)
+    }
+
+    fn decode(&mut self, flush: bool) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut pos = 0;
+        while pos < self.pending.len() {
+            match decode_one(&self.pending[pos..]) {
+                Decoded::Key(key, len) => {
+                    events.push(Event::Key(key));
+                    pos += len;
+                }
+                Decoded::Mouse(mouse, len) => {
+                    events.push(Event::Mouse(mouse));
+                    pos += len;
+                }
+                Decoded::Skip(len) => pos += len,
+                Decoded::Incomplete if flush => {
+                    // An unfinished escape sequence becomes Esc followed by
+                    // its bytes; an unfinished UTF-8 character is dropped.
+                    if self.pending[pos] == ESC {
+                        events.push(Event::Key(key(KeyCode::Esc)));
+                        pos += 1;
+                    } else {
+                        pos = self.pending.len();
+                    }
+                }
+                Decoded::Incomplete => break,
+            }
+        }
+        self.pending.drain(..pos);
+        events
+    }
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn decode_one(buf: &[u8]) -> Decoded {
+    match buf[0] {
+        ESC => decode_escape(buf),
+        b'\r' | b'\n' => Decoded::Key(key(KeyCode::Enter), 1),
+        b'\t' => Decoded::Key(key(KeyCode::Tab), 1),
+        0x7f | 0x08 => Decoded::Key(key(KeyCode::Backspace), 1),
+        0x00 => Decoded::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL), 1),
+        // Ctrl-A .. Ctrl-Z
+        b @ 0x01..=0x1a => {
+            let c = (b - 1 + b'a') as char;
+            Decoded::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL), 1)
+        }
+        // Other C0 controls (Ctrl-\, Ctrl-], ...)
+        0x1c..=0x1f => Decoded::Skip(1),
+        _ => decode_char(buf),
+    }
+}
+
+/// Decode one UTF-8 character.
+fn decode_char(buf: &[u8]) -> Decoded {
+    let len = match buf[0] {
+        0x00..=0x7f => 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        // Continuation or invalid leading byte
+        _ => return Decoded::Skip(1),
+    };
+    let Some(bytes) = buf.get(..len) else {
+        return if buf[1..].iter().all(|b| b & 0xc0 == 0x80) {
+            Decoded::Incomplete
+        } else {
+            Decoded::Skip(1)
+        };
+    };
+    match std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+    {
+        Some(c) => Decoded::Key(key(KeyCode::Char(c)), len),
+        None => Decoded::Skip(1),
+    }
+}
+
+/// Decode input starting with Esc: a CSI or SS3 sequence, an
+/// Alt-modified key, or Esc itself.
+fn decode_escape(buf: &[u8]) -> Decoded {
+    match buf.get(1) {
+        None => Decoded::Incomplete,
+        Some(b'[') => decode_csi(buf),
+        Some(b'O') => decode_ss3(buf),
+        // Esc Esc: the first one cannot start a sequence
+        Some(&ESC) => Decoded::Key(key(KeyCode::Esc), 1),
+        // Esc followed by a key is how terminals send Alt+key
+        Some(_) => match decode_one(&buf[1..]) {
+            Decoded::Key(mut key, len) => {
+                key.modifiers |= KeyModifiers::ALT;
+                Decoded::Key(key, len + 1)
+            }
+            Decoded::Skip(len) | Decoded::Mouse(_, len) => Decoded::Skip(len + 1),
+            Decoded::Incomplete => Decoded::Incomplete,
+        },
+    }
+}
+
+/// Decode `Esc O <final>`, sent for arrows and F1-F4 in application mode.
+fn decode_ss3(buf: &[u8]) -> Decoded {
+    let Some(&last) = buf.get(2) else {
+        return Decoded::Incomplete;
+    };
+    match final_key(last) {
+        Some(code) => Decoded::Key(key(code), 3),
+        None => Decoded::Skip(3),
+    }
+}
+
+/// Decode `Esc [ <params> <final>`.
+fn decode_csi(buf: &[u8]) -> Decoded {
+    // Parameter bytes, then intermediate bytes, then one final byte
+    let body = &buf[2..];
+    let Some(end) = body.iter().position(|b| (0x40..=0x7e).contains(b)) else {
+        // Anything other than parameter/intermediate bytes means the
+        // sequence is malformed; drop just the Esc [ and move on.
+        return if body.iter().all(|b| (0x20..=0x3f).contains(b)) {
+            Decoded::Incomplete
+        } else {
+            Decoded::Skip(2)
+        };
+    };
+    let len = 2 + end + 1;
+    let params = std::str::from_utf8(&body[..end]).unwrap_or("");
+    let last = body[end];
+
+    if let Some(params) = params.strip_prefix('<') {
+        return match decode_sgr_mouse(params, last) {
+            Some(mouse) => Decoded::Mouse(mouse, len),
+            None => Decoded::Skip(len),
+        };
+    }
+
+    let mut fields = params.split(';');
+    let first: Option<u16> = fields.next().and_then(|p| p.parse().ok());
+    let modifiers = fields
+        .next()
+        .and_then(|m| m.parse().ok())
+        .map_or(KeyModifiers::NONE, modifiers_from_param);
+
+    let code = match last {
+        b'~' => match first {
+            Some(1 | 7) => Some(KeyCode::Home),
+            Some(2) => Some(KeyCode::Insert),
+            Some(3) => Some(KeyCode::Delete),
+            Some(4 | 8) => Some(KeyCode::End),
+            Some(5) => Some(KeyCode::PageUp),
+            Some(6) => Some(KeyCode::PageDown),
+            Some(n @ 11..=15) => Some(KeyCode::F((n - 10) as u8)),
+            Some(n @ 17..=21) => Some(KeyCode::F((n - 11) as u8)),
+            Some(n @ 23..=24) => Some(KeyCode::F((n - 12) as u8)),
+            _ => None,
+        },
+        b'Z' => Some(KeyCode::BackTab),
+        _ => final_key(last),
+    };
+
+    match code {
+        Some(KeyCode::BackTab) => {
+            Decoded::Key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT), len)
+        }
+        Some(code) => Decoded::Key(KeyEvent::new(code, modifiers), len),
+        None => Decoded::Skip(len),
+    }
+}
+
+/// Decode the body of an SGR mouse report, `Esc [ < b ; x ; y M|m`.
+/// `M` is a press (or wheel/motion), `m` a release; `x` and `y` are
+/// 1-based.
+fn decode_sgr_mouse(params: &str, last: u8) -> Option<MouseEvent> {
+    let mut fields = params.split(';').map(|f| f.parse::<u16>().ok());
+    let (b, x, y) = (fields.next()??, fields.next()??, fields.next()??);
+
+    let button = match b & 0b11 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        _ => MouseButton::Right,
+    };
+    let kind = if b & 64 != 0 {
+        match b & 0b11 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        }
+    } else if b & 32 != 0 {
+        if b & 0b11 == 3 {
+            MouseEventKind::Moved
+        } else {
+            MouseEventKind::Drag(button)
+        }
+    } else if last == b'm' {
+        MouseEventKind::Up(button)
+    } else if last == b'M' {
+        MouseEventKind::Down(button)
+    } else {
+        return None;
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if b & 4 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if b & 8 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if b & 16 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+
+    Some(MouseEvent {
+        kind,
+        column: x.saturating_sub(1),
+        row: y.saturating_sub(1),
+        modifiers,
+    })
+}
+
+/// Keys identified by the final byte alone, shared by CSI and SS3.
+fn final_key(last: u8) -> Option<KeyCode> {
+    match last {
+        b'A' => Some(KeyCode::Up),
+        b'B' => Some(KeyCode::Down),
+        b'C' => Some(KeyCode::Right),
+        b'D' => Some(KeyCode::Left),
+        b'H' => Some(KeyCode::Home),
+        b'F' => Some(KeyCode::End),
+        b'P' => Some(KeyCode::F(1)),
+        b'Q' => Some(KeyCode::F(2)),
+        b'R' => Some(KeyCode::F(3)),
+        b'S' => Some(KeyCode::F(4)),
+        _ => None,
+    }
+}
+
+/// xterm modifier parameter: 1 + (Shift = 1 | Alt = 2 | Ctrl = 4).
+fn modifiers_from_param(param: u8) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if bits & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if bits & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(events: &[Event]) -> Vec<KeyEvent> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                Event::Key(key) => Some(*key),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn with(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn csi_sequences_carry_their_modifiers() {
+        let mut decoder = InputDecoder::default();
+        let events = decoder.feed(b"\x1b[A\x1b[1;5C\x1b[5~\x1b[15~\x1b[Z");
+        assert_eq!(
+            keys(&events),
+            [
+                key(KeyCode::Up),
+                with(KeyCode::Right, KeyModifiers::CONTROL),
+                key(KeyCode::PageUp),
+                key(KeyCode::F(5)),
+                with(KeyCode::BackTab, KeyModifiers::SHIFT),
+            ]
+        );
+        assert!(!decoder.has_pending());
+    }
+
+    #[test]
+    fn ss3_sequences_are_arrows_and_function_keys() {
+        let mut decoder = InputDecoder::default();
+        let events = decoder.feed(b"\x1bOB\x1bOP");
+        assert_eq!(keys(&events), [key(KeyCode::Down), key(KeyCode::F(1))]);
+    }
+
+    #[test]
+    fn sequences_split_across_packets_are_joined() {
+        let mut decoder = InputDecoder::default();
+        assert!(decoder.feed(b"\x1b[1;").is_empty());
+        assert!(decoder.has_pending());
+        let events = decoder.feed(b"2D");
+        assert_eq!(keys(&events), [with(KeyCode::Left, KeyModifiers::SHIFT)]);
+    }
+
+    #[test]
+    fn utf8_characters_split_across_packets_are_joined() {
+        let mut decoder = InputDecoder::default();
+        let e_acute = "é".as_bytes();
+        assert!(decoder.feed(&e_acute[..1]).is_empty());
+        let events = decoder.feed(&[&e_acute[1..], "→".as_bytes()].concat());
+        assert_eq!(
+            keys(&events),
+            [key(KeyCode::Char('é')), key(KeyCode::Char('→'))]
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_is_skipped() {
+        let mut decoder = InputDecoder::default();
+        let events = decoder.feed(b"\x80a\xc3(");
+        assert_eq!(
+            keys(&events),
+            [key(KeyCode::Char('a')), key(KeyCode::Char('('))]
+        );
+    }
+
+    #[test]
+    fn lone_esc_waits_for_the_timeout() {
+        let mut decoder = InputDecoder::default();
+        assert!(decoder.feed(b"\x1b").is_empty());
+        assert!(!decoder.is_stale());
+
+        decoder.last_input = Instant::now().checked_sub(ESC_TIMEOUT);
+        assert!(decoder.is_stale());
+        assert_eq!(keys(&decoder.flush()), [key(KeyCode::Esc)]);
+        assert!(!decoder.has_pending());
+    }
+
+    #[test]
+    fn esc_before_a_key_is_alt() {
+        let mut decoder = InputDecoder::default();
+        let events = decoder.feed(b"\x1bx\x1b\x1b");
+        assert_eq!(
+            keys(&events),
+            [
+                with(KeyCode::Char('x'), KeyModifiers::ALT),
+                key(KeyCode::Esc)
+            ]
+        );
+        // The second Esc could still start a sequence
+        assert!(decoder.has_pending());
+        assert_eq!(keys(&decoder.flush()), [key(KeyCode::Esc)]);
+    }
+
+    #[test]
+    fn unfinished_sequence_flushes_as_esc_and_its_bytes() {
+        let mut decoder = InputDecoder::default();
+        assert!(decoder.feed(b"\x1b[").is_empty());
+        assert_eq!(
+            keys(&decoder.flush()),
+            [key(KeyCode::Esc), key(KeyCode::Char('['))]
+        );
+    }
+
+    #[test]
+    fn sgr_mouse_reports_are_zero_based() {
+        let mut decoder = InputDecoder::default();
+        let events = decoder.feed(b"\x1b[<0;10;5M\x1b[<65;1;1M");
+        let kinds: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                Event::Mouse(m) => Some((m.kind, m.column, m.row)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (MouseEventKind::Down(MouseButton::Left), 9, 4),
+                (MouseEventKind::ScrollDown, 0, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,581 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+// ── Keymap ─────────────────────────────────────────────────────
+//
+// Navigation keys map to actions through a keymap rather than being
+// matched in the SSH handler, so they can be rebound and so the footer
+// can describe the bindings that are actually in effect.
+//
+// A keymap file is a TOML table from action names to the keys bound to
+// them. Listing an action replaces its default keys; a key bound in the
+// file is taken away from any other action that had it by default:
+//
+//     quit = ["q", "ctrl-c", "esc"]
+//     down = ["j", "down", "ctrl-n"]
+
+/// Something a key can do in normal (navigation) mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    NextTab,
+    PrevTab,
+    /// Scroll up, or select the previous item on tabs with a list.
+    Up,
+    /// Scroll down, or select the next item on tabs with a list.
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    /// Jump to the tab at this index.
+    GoToTab(usize),
+    Search,
+    /// Open the tag filter on a project browser.
+    Filter,
+    /// Open the selected post.
+    Open,
+    /// Close the post reader.
+    Back,
+    /// Show every binding.
+    Help,
+    /// Open the command palette.
+    Command,
+    /// Copy the selected URL or contact value to the clipboard.
+    Copy,
+    /// Show the contact card as a QR code.
+    ContactCard,
+    /// Switch between Unicode and ASCII-only drawing.
+    ToggleAscii,
+    /// Switch to the next colour theme.
+    CycleTheme,
+    /// Choose a colour theme from a list.
+    PickTheme,
+}
+
+impl Action {
+    /// Every action with a fixed name, in the order they are documented.
+    const NAMED: &[(&str, Action)] = &[
+        ("quit", Action::Quit),
+        ("next_tab", Action::NextTab),
+        ("prev_tab", Action::PrevTab),
+        ("up", Action::Up),
+        ("down", Action::Down),
+        ("page_up", Action::PageUp),
+        ("page_down", Action::PageDown),
+        ("top", Action::Top),
+        ("bottom", Action::Bottom),
+        ("search", Action::Search),
+        ("filter", Action::Filter),
+        ("open", Action::Open),
+        ("back", Action::Back),
+        ("help", Action::Help),
+        ("command", Action::Command),
+        ("copy", Action::Copy),
+        ("contact_card", Action::ContactCard),
+        ("toggle_ascii", Action::ToggleAscii),
+        ("cycle_theme", Action::CycleTheme),
+        ("pick_theme", Action::PickTheme),
+    ];
+
+    /// Number of tabs that have a `tab_<n>` action.
+    const TAB_ACTIONS: usize = 10;
+
+    /// What the action does, for the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextTab => "next tab",
+            Action::PrevTab => "previous tab",
+            Action::Up => "scroll up / previous item",
+            Action::Down => "scroll down / next item",
+            Action::PageUp => "page up",
+            Action::PageDown => "page down",
+            Action::Top => "top / first item",
+            Action::Bottom => "bottom / last item",
+            Action::GoToTab(_) => "jump to tab",
+            Action::Search => "search",
+            Action::Filter => "filter by technology",
+            Action::Open => "open post",
+            Action::Back => "back to the post list",
+            Action::Help => "show this help",
+            Action::Command => "command palette",
+            Action::Copy => "copy URL or value",
+            Action::ContactCard => "add to contacts (QR code)",
+            Action::ToggleAscii => "ASCII-only drawing on/off",
+            Action::CycleTheme => "next colour theme",
+            Action::PickTheme => "choose a colour theme",
+        }
+    }
+
+    /// Parse an action name as used in keymap files, e.g. `next_tab` or
+    /// `tab_3`.
+    fn from_name(name: &str) -> Option<Action> {
+        if let Some(n) = name.strip_prefix("tab_") {
+            return match n.parse::<usize>() {
+                Ok(n @ 1..=Self::TAB_ACTIONS) => Some(Action::GoToTab(n - 1)),
+                _ => None,
+            };
+        }
+        Self::NAMED
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// A key, with the modifiers that must be held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    const fn char(c: char) -> Self {
+        Self::plain(KeyCode::Char(c))
+    }
+
+    /// The binding a key event corresponds to. Shift is already part of
+    /// a typed character (`G` vs `g`) and of Shift-Tab, so it is ignored
+    /// for those.
+    fn from_event(key: &KeyEvent) -> Self {
+        let mut modifiers = key.modifiers;
+        if matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self::new(key.code, modifiers)
+    }
+
+    /// Parse a key as written in keymap files: a character (`q`, `G`,
+    /// `?`) or a key name (`up`, `pagedown`, `f1`, `space`), optionally
+    /// prefixed with `ctrl-`, `alt-` and/or `shift-`.
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let mut rest = s;
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let (prefix, modifier) = if lower.starts_with("ctrl-") {
+                ("ctrl-", KeyModifiers::CONTROL)
+            } else if lower.starts_with("alt-") {
+                ("alt-", KeyModifiers::ALT)
+            } else if lower.starts_with("shift-") {
+                ("shift-", KeyModifiers::SHIFT)
+            } else {
+                break;
+            };
+            // A lone "-" after the prefix is the minus key, not a modifier
+            if rest.len() == prefix.len() {
+                break;
+            }
+            modifiers |= modifier;
+            rest = &rest[prefix.len()..];
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "insert" => KeyCode::Insert,
+                "delete" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+                    _ => bail!("unknown key {s:?}"),
+                },
+            },
+        };
+
+        // Ctrl-letters arrive lowercase whatever the shift state
+        if modifiers.contains(KeyModifiers::CONTROL)
+            && let KeyCode::Char(c) = code
+        {
+            return Ok(Self::new(KeyCode::Char(c.to_ascii_lowercase()), modifiers));
+        }
+        Ok(Self::from_event(&KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Short form for hints, e.g. `ctrl-c`, `shift-tab`, `pgdn`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Up => f.write_str("\u{2191}"),
+            KeyCode::Down => f.write_str("\u{2193}"),
+            KeyCode::Left => f.write_str("\u{2190}"),
+            KeyCode::Right => f.write_str("\u{2192}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("shift-tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pgup"),
+            KeyCode::PageDown => f.write_str("pgdn"),
+            KeyCode::Insert => f.write_str("ins"),
+            KeyCode::Delete => f.write_str("del"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            _ => f.write_str("?"),
+        }
+    }
+}
+
+/// Keys of the search prompt. These are fixed rather than configurable,
+/// since the prompt takes typed text; see `handler::search_key`.
+const SEARCH_HELP: &[(&str, &str)] = &[
+    ("\u{2191} ctrl-p", "previous result"),
+    ("\u{2193} ctrl-n", "next result"),
+    ("enter", "jump to result"),
+    ("ctrl-u", "clear the query"),
+    ("esc ctrl-c", "cancel"),
+];
+
+/// Keys of the command palette, fixed like those of the search prompt;
+/// see `handler::command_key`.
+const COMMAND_HELP: &[(&str, &str)] = &[
+    ("tab", "complete"),
+    ("enter", "run"),
+    ("ctrl-u", "clear"),
+    ("esc ctrl-c", "cancel"),
+];
+
+/// A titled group of bindings in the help overlay.
+#[derive(Debug, Clone)]
+pub struct HelpSection {
+    pub title: &'static str,
+    /// Keys, and what they do.
+    pub entries: Vec<(String, &'static str)>,
+}
+
+/// Key bindings for normal mode.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Bindings in priority order; the first binding of an action is the
+    /// one shown in hints.
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use KeyBinding as K;
+        let ctrl = KeyModifiers::CONTROL;
+
+        let mut bindings = vec![
+            (K::char('q'), Action::Quit),
+            (K::char('Q'), Action::Quit),
+            (K::new(KeyCode::Char('c'), ctrl), Action::Quit),
+            (K::char('h'), Action::PrevTab),
+            (K::plain(KeyCode::Left), Action::PrevTab),
+            (K::plain(KeyCode::BackTab), Action::PrevTab),
+            (K::char('l'), Action::NextTab),
+            (K::plain(KeyCode::Right), Action::NextTab),
+            (K::plain(KeyCode::Tab), Action::NextTab),
+            (K::char('j'), Action::Down),
+            (K::plain(KeyCode::Down), Action::Down),
+            (K::char('k'), Action::Up),
+            (K::plain(KeyCode::Up), Action::Up),
+            (K::plain(KeyCode::PageUp), Action::PageUp),
+            (K::plain(KeyCode::PageDown), Action::PageDown),
+            (K::char('g'), Action::Top),
+            (K::plain(KeyCode::Home), Action::Top),
+            (K::char('G'), Action::Bottom),
+            (K::plain(KeyCode::End), Action::Bottom),
+            (K::char('/'), Action::Search),
+            (K::char('f'), Action::Filter),
+            (K::plain(KeyCode::Enter), Action::Open),
+            (K::plain(KeyCode::Esc), Action::Back),
+            (K::char('?'), Action::Help),
+            (K::char(':'), Action::Command),
+            (K::char('y'), Action::Copy),
+            (K::char('v'), Action::ContactCard),
+            (K::char('u'), Action::ToggleAscii),
+            (K::char('t'), Action::CycleTheme),
+            (K::char('T'), Action::PickTheme),
+        ];
+        // '1' .. '9', '0' jump to the first ten tabs
+        for n in 0..Action::TAB_ACTIONS {
+            let digit = char::from_digit(((n + 1) % 10) as u32, 10).unwrap_or('0');
+            bindings.push((K::char(digit), Action::GoToTab(n)));
+        }
+
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Load a keymap file, applied on top of the default bindings.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&raw).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Parse a keymap from TOML source.
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let table: HashMap<String, Vec<String>> = toml::from_str(raw)?;
+
+        let mut overrides: Vec<(KeyBinding, Action)> = Vec::new();
+        for (name, keys) in &table {
+            let Some(action) = Action::from_name(name) else {
+                bail!("unknown action {name:?}");
+            };
+            for key in keys {
+                let binding = KeyBinding::parse(key).with_context(|| format!("in {name:?}"))?;
+                if let Some((_, other)) = overrides.iter().find(|(b, _)| *b == binding) {
+                    bail!(
+                        "key {key:?} is bound to both {name:?} and {}",
+                        name_of(*other)
+                    );
+                }
+                overrides.push((binding, action));
+            }
+        }
+
+        // Configured actions lose their defaults, and configured keys are
+        // taken away from whatever they did before.
+        let mut keymap = Self::default();
+        keymap.bindings.retain(|(binding, action)| {
+            !overrides.iter().any(|(b, a)| a == action || b == binding)
+        });
+        keymap.bindings.extend(overrides);
+        Ok(keymap)
+    }
+
+    /// The action bound to a key event, if any.
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        let binding = KeyBinding::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(b, _)| *b == binding)
+            .map(|(_, action)| *action)
+    }
+
+    /// Every key bound to `action`, in priority order.
+    pub fn keys_for(&self, action: Action) -> Vec<KeyBinding> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(b, _)| *b)
+            .collect()
+    }
+
+    /// The key to show for `action` in hints, if it has one.
+    pub fn hint(&self, action: Action) -> Option<String> {
+        self.keys_for(action).first().map(ToString::to_string)
+    }
+
+    /// Every binding, grouped by where it applies, for the help overlay.
+    /// Tab jumps are listed for the first `tab_count` tabs only.
+    pub fn help(&self, tab_count: usize) -> Vec<HelpSection> {
+        let entries = |actions: &[Action]| -> Vec<(String, &'static str)> {
+            actions
+                .iter()
+                .filter_map(|&action| {
+                    let keys = self.keys_for(action);
+                    if keys.is_empty() {
+                        return None;
+                    }
+                    let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+                    Some((keys.join(" "), action.description()))
+                })
+                .collect()
+        };
+
+        let fixed = |keys: &[(&str, &'static str)]| -> Vec<(String, &'static str)> {
+            keys.iter()
+                .map(|(keys, description)| (keys.to_string(), *description))
+                .collect()
+        };
+
+        let mut global = entries(&[Action::PrevTab, Action::NextTab]);
+        let tab_keys: Vec<String> = (0..tab_count.min(Action::TAB_ACTIONS))
+            .filter_map(|n| self.hint(Action::GoToTab(n)))
+            .collect();
+        if !tab_keys.is_empty() {
+            global.push((tab_keys.join(" "), Action::GoToTab(0).description()));
+        }
+        global.extend(entries(&[
+            Action::Up,
+            Action::Down,
+            Action::PageUp,
+            Action::PageDown,
+            Action::Top,
+            Action::Bottom,
+            Action::Search,
+            Action::Command,
+            Action::CycleTheme,
+            Action::PickTheme,
+            Action::ToggleAscii,
+            Action::Help,
+            Action::Quit,
+        ]));
+
+        let mut sections = vec![
+            HelpSection {
+                title: "Global",
+                entries: global,
+            },
+            HelpSection {
+                title: "Projects",
+                entries: entries(&[Action::Filter, Action::Copy]),
+            },
+            HelpSection {
+                title: "Contact",
+                entries: entries(&[Action::Copy, Action::ContactCard]),
+            },
+            HelpSection {
+                title: "Posts",
+                entries: entries(&[Action::Open, Action::Back]),
+            },
+            HelpSection {
+                title: "Search",
+                entries: fixed(SEARCH_HELP),
+            },
+            HelpSection {
+                title: "Command palette",
+                entries: fixed(COMMAND_HELP),
+            },
+        ];
+        // Every binding of a section may have been taken by another action
+        sections.retain(|section| !section.entries.is_empty());
+        sections
+    }
+}
+
+/// Name of an action as written in keymap files.
+fn name_of(action: Action) -> String {
+    match action {
+        Action::GoToTab(n) => format!("\"tab_{}\"", n + 1),
+        action => Action::NAMED
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map_or_else(String::new, |(name, _)| format!("{name:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn char(c: char) -> KeyEvent {
+        press(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn keys_parse_with_modifiers() {
+        let ctrl = KeyModifiers::CONTROL;
+        assert_eq!(KeyBinding::parse("q").unwrap(), KeyBinding::char('q'));
+        assert_eq!(
+            KeyBinding::parse("PageDown").unwrap(),
+            KeyBinding::plain(KeyCode::PageDown)
+        );
+        assert_eq!(
+            KeyBinding::parse("f12").unwrap(),
+            KeyBinding::plain(KeyCode::F(12))
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl-N").unwrap(),
+            KeyBinding::new(KeyCode::Char('n'), ctrl)
+        );
+        assert_eq!(
+            KeyBinding::parse("shift-tab").unwrap(),
+            KeyBinding::plain(KeyCode::BackTab)
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl--").unwrap(),
+            KeyBinding::new(KeyCode::Char('-'), ctrl)
+        );
+        assert_eq!(KeyBinding::parse("shift-g").unwrap(), KeyBinding::char('g'));
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(KeyBinding::parse("hyper-x").is_err());
+    }
+
+    #[test]
+    fn shift_is_ignored_for_typed_characters() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Some(Action::Bottom)
+        );
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some(Action::PrevTab)
+        );
+        assert_eq!(keymap.action_for(&char('0')), Some(Action::GoToTab(9)));
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Down, KeyModifiers::SHIFT)),
+            None
+        );
+    }
+
+    #[test]
+    fn configured_actions_replace_their_defaults() {
+        let keymap = Keymap::parse("down = [\"n\", \"down\"]").unwrap();
+        assert_eq!(keymap.action_for(&char('n')), Some(Action::Down));
+        assert_eq!(keymap.action_for(&char('j')), None);
+        assert_eq!(keymap.hint(Action::Down).as_deref(), Some("n"));
+        assert_eq!(keymap.hint(Action::Up).as_deref(), Some("k"));
+    }
+
+    #[test]
+    fn configured_keys_are_taken_from_other_actions() {
+        let keymap = Keymap::parse("tab_3 = [\"q\"]").unwrap();
+        assert_eq!(keymap.action_for(&char('q')), Some(Action::GoToTab(2)));
+        assert_eq!(keymap.action_for(&char('3')), None);
+        assert_eq!(keymap.hint(Action::Quit).as_deref(), Some("Q"));
+    }
+
+    #[test]
+    fn bad_keymaps_are_rejected() {
+        assert!(Keymap::parse("jump = [\"j\"]").is_err());
+        assert!(Keymap::parse("tab_11 = [\"x\"]").is_err());
+        assert!(Keymap::parse("up = [\"nope\"]").is_err());
+        let err = Keymap::parse("up = [\"x\"]\ndown = [\"x\"]").unwrap_err();
+        assert!(err.to_string().contains("is bound to both"), "{err}");
+    }
+}
@@ -1,18 +1,47 @@
 mod app;
+mod base16;
+mod caps;
+mod clipboard;
+mod command;
 mod content;
+mod export;
+mod glyphs;
 mod handler;
+mod hyperlink;
+mod input;
+mod keymap;
+mod markdown;
+mod portfolio;
+mod posts;
+mod search;
 mod terminal;
 mod theme;
 mod ui;
+mod validate;
+mod vcard;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use russh::keys::ssh_key::rand_core::OsRng;
 
 use handler::AppServer;
+use keymap::Keymap;
+use portfolio::Portfolio;
+use theme::Scheme;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // `validate [path]` checks a content file and exits instead of serving
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("validate") {
+        let Some(path) = args.next().map(PathBuf::from).or_else(content_path) else {
+            eprintln!("usage: ssh-yannickh-dev validate <content-file>");
+            std::process::exit(2);
+        };
+        std::process::exit(validate::run(&path));
+    }
+
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
     let port: u16 = std::env::var("PORT")
@@ -30,6 +59,38 @@ async fn main() -> anyhow::Result<()> {
             .expect("Failed to generate host key")
     };
 
+    let content_path = content_path();
+    let portfolio = match &content_path {
+        Some(path) => {
+            log::info!("Loading portfolio content from {}...", path.display());
+            Portfolio::load(path)?
+        }
+        None => {
+            log::info!("No content file set, using built-in portfolio content...");
+            Portfolio::builtin()
+        }
+    };
+
+    let keymap = match keymap_path() {
+        Some(path) => {
+            log::info!("Loading key bindings from {}...", path.display());
+            Keymap::load(&path)?
+        }
+        None => Keymap::default(),
+    };
+
+    let mut schemes: Vec<Scheme> = theme::BUNDLED.to_vec();
+    if let Some(dir) = themes_path() {
+        log::info!("Loading Base16 themes from {}...", dir.display());
+        for scheme in base16::load_dir(&dir)? {
+            if theme::find(&schemes, &scheme.name).is_some() {
+                log::warn!("Skipping theme {}: the name is taken", scheme.name);
+                continue;
+            }
+            schemes.push(scheme);
+        }
+    }
+
     let config = russh::server::Config {
         inactivity_timeout: Some(std::time::Duration::from_secs(300)),
         auth_rejection_time: std::time::Duration::from_secs(1),
@@ -39,7 +100,11 @@ async fn main() -> anyhow::Result<()> {
         ..Default::default()
     };
 
-    let mut server = AppServer::new();
+    let mut server = AppServer::new(portfolio, keymap, schemes);
+    if let Some(path) = content_path {
+        log::info!("Watching {} for changes", path.display());
+        server.spawn_content_watcher(path);
+    }
 
     log::info!("SSH portfolio server listening on 0.0.0.0:{port}");
     log::info!("Connect with: ssh localhost -p {port}");
@@ -48,3 +113,35 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Path of the portfolio content file, from `--content <path>` or the
+/// `CONTENT_FILE` env var.
+fn content_path() -> Option<PathBuf> {
+    path_option("--content", "CONTENT_FILE")
+}
+
+/// Path of the key bindings file, from `--keymap <path>` or the
+/// `KEYMAP_FILE` env var.
+fn keymap_path() -> Option<PathBuf> {
+    path_option("--keymap", "KEYMAP_FILE")
+}
+
+/// Directory of Base16 theme files, from `--themes <path>` or the
+/// `THEMES_DIR` env var.
+fn themes_path() -> Option<PathBuf> {
+    path_option("--themes", "THEMES_DIR")
+}
+
+/// A path given as `<flag> <path>`, `<flag>=<path>` or in `env`.
+fn path_option(flag: &str, env: &str) -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix(flag).and_then(|a| a.strip_prefix('=')) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    std::env::var_os(env).map(PathBuf::from)
+}
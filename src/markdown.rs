@@ -0,0 +1,376 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::UnicodeWidthStr;
+
+use crate::theme::Theme;
+
+// ── Markdown → styled lines ────────────────────────────────────
+//
+// Renders the subset of Markdown used for About pages and project
+// descriptions: headings, emphasis, inline code, links, (nested) lists,
+// block quotes, rules and code blocks. Prose is reflowed to the given
+// width; code blocks are kept verbatim.
+
+/// Render Markdown `source` into lines wrapped to `width` columns, in
+/// the styles of `theme`.
+pub fn render(source: &str, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(width, *theme);
+    for event in Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH) {
+        renderer.event(event);
+    }
+    renderer.flush();
+    renderer.lines
+}
+
+/// A run of non-whitespace text, possibly spanning several styles
+/// (e.g. `**bold**,` is one word made of two fragments).
+type Word = Vec<(String, Style)>;
+
+enum Token {
+    Word(Word),
+    /// Hard line break.
+    Break,
+}
+
+struct Renderer {
+    width: usize,
+    theme: Theme,
+    lines: Vec<Line<'static>>,
+    /// Inline text of the block being built, flushed at block end.
+    inline: Vec<(String, Style)>,
+    styles: Vec<Style>,
+    /// Destination of the link being built, if any.
+    link: Option<String>,
+    /// Next number for each open list (`None` for bullet lists).
+    lists: Vec<Option<u64>>,
+    /// Indent width of each open list item.
+    items: Vec<usize>,
+    /// Marker of a list item whose first line has not been written yet.
+    pending_marker: Option<String>,
+    quote_depth: usize,
+    code_block: Option<String>,
+    /// Whether the next block should be separated by a blank line.
+    needs_gap: bool,
+}
+
+impl Renderer {
+    fn new(width: usize, theme: Theme) -> Self {
+        Self {
+            width,
+            theme,
+            lines: Vec::new(),
+            inline: Vec::new(),
+            styles: vec![theme.text],
+            link: None,
+            lists: Vec::new(),
+            items: Vec::new(),
+            pending_marker: None,
+            quote_depth: 0,
+            code_block: None,
+            needs_gap: false,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or(self.theme.text)
+    }
+
+    fn push_style(&mut self, f: impl FnOnce(Style) -> Style) {
+        let style = f(self.style());
+        self.styles.push(style);
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code_block {
+                Some(code) => code.push_str(&text),
+                None => self.inline.push((text.into_string(), self.style())),
+            },
+            Event::Code(code) => self.inline.push((code.into_string(), self.theme.md_code)),
+            Event::SoftBreak => self.inline.push((" ".to_string(), self.style())),
+            Event::HardBreak => self.inline.push(("\n".to_string(), self.style())),
+            Event::Rule => {
+                self.flush();
+                self.gap();
+                let rule = "\u{2500}".repeat(self.width.saturating_sub(self.indent()));
+                let mut spans = self.prefix(false);
+                spans.push(Span::styled(rule, self.theme.text_muted));
+                self.lines.push(Line::from(spans));
+                self.needs_gap = true;
+            }
+            // Raw HTML, footnotes, math, etc. are outside the subset
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let style = if level == HeadingLevel::H1 {
+                    self.theme.text_bold
+                } else {
+                    self.theme.md_subheading
+                };
+                self.styles.push(style);
+            }
+            Tag::Emphasis => self.push_style(|s| s.add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(|s| s.add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(|s| s.add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.link = Some(dest_url.into_string());
+                self.styles.push(self.theme.link);
+            }
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                // Text of the enclosing item goes before the nested list
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        let marker = format!("{n}. ");
+                        *n += 1;
+                        marker
+                    }
+                    _ => "\u{2022} ".to_string(),
+                };
+                self.items.push(marker.width());
+                self.pending_marker = Some(marker);
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.gap();
+                self.quote_depth += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush();
+                self.needs_gap = true;
+            }
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.styles.pop();
+                self.needs_gap = true;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                if let Some(url) = self.link.take() {
+                    // Show the destination unless the text already is the URL
+                    let text: String = self.inline.iter().map(|(t, _)| t.as_str()).collect();
+                    if !text.ends_with(url.trim_start_matches("mailto:")) {
+                        self.inline.push((format!(" <{url}>"), self.theme.text_muted));
+                    }
+                }
+            }
+            TagEnd::CodeBlock => {
+                if let Some(code) = self.code_block.take() {
+                    self.gap();
+                    // The first line carries the marker of an item that
+                    // starts with the code block
+                    for (i, line) in code.trim_end_matches('\n').lines().enumerate() {
+                        let mut spans = self.prefix(i == 0);
+                        spans.push(Span::styled(format!("  {line}"), self.theme.md_code_block));
+                        self.lines.push(Line::from(spans));
+                    }
+                    self.needs_gap = true;
+                }
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.needs_gap = true;
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.items.pop();
+                self.pending_marker = None;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.needs_gap = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Push a blank separator line if the previous block asked for one.
+    fn gap(&mut self) {
+        if self.needs_gap && !self.lines.is_empty() {
+            let spans = self.prefix(false);
+            self.lines.push(Line::from(spans));
+        }
+        self.needs_gap = false;
+    }
+
+    /// Width taken by quote bars and list indentation.
+    fn indent(&self) -> usize {
+        self.quote_depth * 2 + self.items.iter().sum::<usize>()
+    }
+
+    /// Spans to put in front of a line: quote bars, then the list item
+    /// marker on the item's first line or a hanging indent after it.
+    fn prefix(&mut self, first_line: bool) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for _ in 0..self.quote_depth {
+            spans.push(Span::styled("\u{2502} ", self.theme.md_quote));
+        }
+
+        let indent: usize = self.items.iter().sum();
+        let marker = if first_line {
+            self.pending_marker.take()
+        } else {
+            None
+        };
+        match marker {
+            Some(marker) => {
+                let outer = indent - marker.width();
+                if outer > 0 {
+                    spans.push(Span::raw(" ".repeat(outer)));
+                }
+                spans.push(Span::styled(marker, self.theme.md_bullet));
+            }
+            None if indent > 0 => spans.push(Span::raw(" ".repeat(indent))),
+            None => {}
+        }
+        spans
+    }
+
+    /// Reflow the pending inline text into lines.
+    fn flush(&mut self) {
+        if self.inline.is_empty() {
+            return;
+        }
+        let tokens = tokenize(std::mem::take(&mut self.inline));
+        if tokens.is_empty() {
+            return;
+        }
+        self.gap();
+
+        let available = self.width.saturating_sub(self.indent()).max(1);
+        let mut line = self.prefix(true);
+        let mut line_w = 0;
+
+        for token in tokens {
+            let word = match token {
+                Token::Word(word) => word,
+                Token::Break => {
+                    let next = self.prefix(false);
+                    self.lines
+                        .push(Line::from(std::mem::replace(&mut line, next)));
+                    line_w = 0;
+                    continue;
+                }
+            };
+
+            let word_w: usize = word.iter().map(|(t, _)| t.width()).sum();
+            if line_w > 0 && line_w + 1 + word_w > available {
+                let next = self.prefix(false);
+                self.lines
+                    .push(Line::from(std::mem::replace(&mut line, next)));
+                line_w = 0;
+            }
+            if line_w > 0 {
+                line.push(Span::raw(" "));
+                line_w += 1;
+            }
+
+            for (text, style) in word {
+                // Words longer than a whole line are split wherever they overflow
+                let mut chunk = String::new();
+                for ch in text.chars() {
+                    let ch_w = ch.to_string().width();
+                    if line_w + ch_w > available && line_w > 0 {
+                        if !chunk.is_empty() {
+                            line.push(Span::styled(std::mem::take(&mut chunk), style));
+                        }
+                        let next = self.prefix(false);
+                        self.lines
+                            .push(Line::from(std::mem::replace(&mut line, next)));
+                        line_w = 0;
+                    }
+                    chunk.push(ch);
+                    line_w += ch_w;
+                }
+                if !chunk.is_empty() {
+                    line.push(Span::styled(chunk, style));
+                }
+            }
+        }
+        self.lines.push(Line::from(line));
+    }
+}
+
+/// Split styled text into words, collapsing whitespace between them.
+fn tokenize(fragments: Vec<(String, Style)>) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word: Word = Vec::new();
+
+    fn end_word(word: &mut Word, tokens: &mut Vec<Token>) {
+        if !word.is_empty() {
+            tokens.push(Token::Word(std::mem::take(word)));
+        }
+    }
+
+    for (text, style) in fragments {
+        for ch in text.chars() {
+            if ch == '\n' {
+                end_word(&mut word, &mut tokens);
+                tokens.push(Token::Break);
+            } else if ch.is_whitespace() {
+                end_word(&mut word, &mut tokens);
+            } else {
+                match word.last_mut() {
+                    Some((frag, s)) if *s == style => frag.push(ch),
+                    _ => word.push((ch.to_string(), style)),
+                }
+            }
+        }
+    }
+    end_word(&mut word, &mut tokens);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn code_block_first_in_a_list_item_keeps_the_marker() {
+        let source = "1. ```\n   cargo run\n   ```\n2. done\n";
+        let lines = render(source, 40, &Theme::default());
+        assert_eq!(text(&lines), ["1.   cargo run", "   ", "2. done"]);
+    }
+
+    #[test]
+    fn prose_is_wrapped_under_the_marker() {
+        let lines = render("- one two three", 10, &Theme::default());
+        assert_eq!(text(&lines), ["\u{2022} one two", "  three"]);
+    }
+}
@@ -0,0 +1,507 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::content;
+use crate::posts::{self, Post};
+
+// ── Portfolio model ────────────────────────────────────────────
+//
+// Owned equivalents of the static tables in `content.rs`, so the
+// portfolio can be loaded (and reloaded) from a TOML file instead of
+// being baked into the binary. The constants remain the built-in
+// default when no content file is configured.
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Portfolio {
+    pub banner: Vec<String>,
+    /// Full name of the portfolio's owner, used for the contact card.
+    pub name: String,
+    /// Tabs in display order; number keys jump to them in this order.
+    pub tabs: Vec<Tab>,
+}
+
+/// A tab definition. The `kind` key in the content file selects how the
+/// tab is rendered and navigated.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Tab {
+    /// A scrollable page of Markdown.
+    Text { title: String, body: String },
+    /// Telescope-style project browser.
+    Projects {
+        title: String,
+        categories: Vec<ProjectCategory>,
+    },
+    /// Work history as a vertical timeline with a detail pane.
+    Timeline { title: String, entries: Vec<Role> },
+    /// Blog posts read from a directory of Markdown files, with a
+    /// reader view. `dir` is relative to the content file.
+    Posts {
+        title: String,
+        dir: PathBuf,
+        #[serde(skip)]
+        posts: Vec<Post>,
+    },
+    /// Groups of skills, one line of items per group.
+    Skills {
+        title: String,
+        groups: Vec<SkillGroup>,
+    },
+    /// A list of labelled values between an intro and an outro line.
+    KeyValue {
+        title: String,
+        #[serde(default)]
+        intro: String,
+        entries: Vec<ContactEntry>,
+        #[serde(default)]
+        outro: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    /// Short description, in Markdown.
+    pub description: String,
+    pub tech: String,
+    pub url: String,
+}
+
+impl Project {
+    /// Technology tags, parsed from the comma-separated `tech` string.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tech.split(',').map(str::trim).filter(|t| !t.is_empty())
+    }
+
+    /// Whether the project passes a tag filter (`None` lets everything
+    /// through). Tags compare case-insensitively.
+    pub fn matches(&self, filter: Option<&str>) -> bool {
+        match filter {
+            Some(tag) => self.tags().any(|t| t.eq_ignore_ascii_case(tag)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectCategory {
+    pub name: String,
+    pub projects: Vec<Project>,
+}
+
+/// A position in the work history, most recent first.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Role {
+    pub title: String,
+    /// Employer or client.
+    pub client: String,
+    pub start: String,
+    /// End of the role; `None` while it is ongoing.
+    #[serde(default)]
+    pub end: Option<String>,
+    /// Longer description, in Markdown.
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+}
+
+impl Role {
+    /// Human-readable date range, e.g. "2021 – Present".
+    pub fn dates(&self) -> String {
+        format!(
+            "{} \u{2013} {}",
+            self.start,
+            self.end.as_deref().unwrap_or("Present")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillGroup {
+    pub name: String,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContactEntry {
+    pub label: String,
+    pub value: String,
+}
+
+impl Default for Portfolio {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Portfolio {
+    /// The portfolio compiled into the binary (see `content.rs`).
+    pub fn builtin() -> Self {
+        Self {
+            banner: content::BANNER.iter().map(|l| l.to_string()).collect(),
+            name: content::NAME.to_string(),
+            tabs: vec![
+                Tab::Text {
+                    title: "About".to_string(),
+                    body: content::ABOUT.to_string(),
+                },
+                Tab::Projects {
+                    title: "Projects".to_string(),
+                    categories: content::PROJECT_CATEGORIES.iter().map(Into::into).collect(),
+                },
+                Tab::Skills {
+                    title: "Skills".to_string(),
+                    groups: content::SKILLS.iter().map(Into::into).collect(),
+                },
+                Tab::KeyValue {
+                    title: "Contact".to_string(),
+                    intro: content::CONTACT_INTRO.to_string(),
+                    entries: content::CONTACT_ENTRIES.iter().map(Into::into).collect(),
+                    outro: content::CONTACT_OUTRO.to_string(),
+                },
+            ],
+        }
+    }
+
+    /// Load a portfolio from a TOML file, along with the posts of any
+    /// posts tabs. Sections missing from the file fall back to the
+    /// built-in content.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let mut portfolio =
+            Self::parse(&raw).with_context(|| format!("failed to parse {}", path.display()))?;
+        portfolio.load_posts(path.parent().unwrap_or(Path::new(".")))?;
+        Ok(portfolio)
+    }
+
+    /// Read the posts of every posts tab, resolving their directories
+    /// against `base`.
+    pub fn load_posts(&mut self, base: &Path) -> anyhow::Result<()> {
+        for tab in &mut self.tabs {
+            if let Tab::Posts { dir, posts, .. } = tab {
+                *dir = base.join(&*dir);
+                *posts = posts::load_dir(dir)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Files whose changes should trigger a reload, besides the content
+    /// file itself: post directories and the posts in them.
+    pub fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for tab in &self.tabs {
+            if let Tab::Posts { dir, .. } = tab {
+                files.push(dir.clone());
+                files.extend(posts::files_in(dir));
+            }
+        }
+        files
+    }
+
+    /// Parse a portfolio from TOML source.
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(raw)?)
+    }
+
+    // ── Banner ─────────────────────────────────────────────────
+
+    /// Total number of characters in the banner (for the typewriter animation).
+    pub fn banner_char_count(&self) -> usize {
+        self.banner.iter().map(|l| l.chars().count()).sum::<usize>() + self.banner.len() // +newlines
+    }
+}
+
+impl Tab {
+    pub fn title(&self) -> &str {
+        match self {
+            Tab::Text { title, .. }
+            | Tab::Projects { title, .. }
+            | Tab::Timeline { title, .. }
+            | Tab::Posts { title, .. }
+            | Tab::Skills { title, .. }
+            | Tab::KeyValue { title, .. } => title,
+        }
+    }
+}
+
+// ── Projects ───────────────────────────────────────────────────
+//
+// Flat project indices count only the projects that pass the active
+// tag filter, in category order.
+
+/// Number of lines the matching projects take when laid out one after
+/// another. Categories without a match are left out.
+pub fn total_project_lines(categories: &[ProjectCategory], filter: Option<&str>) -> usize {
+    let mut lines = 0;
+    let visible = categories
+        .iter()
+        .filter(|cat| cat.projects.iter().any(|p| p.matches(filter)));
+    for (i, cat) in visible.enumerate() {
+        if i > 0 {
+            lines += 1; // blank separator between categories
+        }
+        lines += 1; // category header
+        lines += 1; // blank after header
+        let projects = cat.projects.iter().filter(|p| p.matches(filter));
+        for (j, _) in projects.enumerate() {
+            if j > 0 {
+                lines += 1; // blank between projects
+            }
+            lines += 4; // name, desc, tech, url
+        }
+    }
+    lines
+}
+
+/// Total number of matching projects across all categories.
+pub fn total_project_count(categories: &[ProjectCategory], filter: Option<&str>) -> usize {
+    categories
+        .iter()
+        .flat_map(|cat| &cat.projects)
+        .filter(|p| p.matches(filter))
+        .count()
+}
+
+/// Map a flat project index (0-based) to the corresponding category and project.
+/// Returns `None` if the index is out of range.
+pub fn get_project_by_flat_index<'a>(
+    categories: &'a [ProjectCategory],
+    idx: usize,
+    filter: Option<&str>,
+) -> Option<(&'a ProjectCategory, &'a Project)> {
+    categories
+        .iter()
+        .flat_map(|cat| cat.projects.iter().map(move |p| (cat, p)))
+        .filter(|(_, p)| p.matches(filter))
+        .nth(idx)
+}
+
+/// Translate a flat index under filter `from` into the flat index of the
+/// same project under filter `to`, if it passes both.
+pub fn remap_project_index(
+    categories: &[ProjectCategory],
+    idx: usize,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Option<usize> {
+    let (_, project) = get_project_by_flat_index(categories, idx, from)?;
+    categories
+        .iter()
+        .flat_map(|cat| &cat.projects)
+        .filter(|p| p.matches(to))
+        .position(|p| std::ptr::eq(p, project))
+}
+
+/// Every tag used by the projects, sorted, with case-insensitive
+/// duplicates merged (the first spelling wins).
+pub fn all_tags(categories: &[ProjectCategory]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in categories.iter().flat_map(|cat| &cat.projects).flat_map(Project::tags) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
+}
+
+// ── Live content ───────────────────────────────────────────────
+
+/// Shared handle to the portfolio currently being served. Reloading
+/// swaps the inner `Arc`, so sessions pick up the new content on their
+/// next frame while anything still holding the old one keeps it alive.
+#[derive(Clone)]
+pub struct SharedPortfolio(Arc<RwLock<Arc<Portfolio>>>);
+
+impl SharedPortfolio {
+    pub fn new(portfolio: Portfolio) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(portfolio))))
+    }
+
+    /// The portfolio as of now.
+    pub fn current(&self) -> Arc<Portfolio> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Atomically replace the live portfolio.
+    pub fn replace(&self, portfolio: Portfolio) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(portfolio);
+    }
+}
+
+// ── Conversions from the built-in tables ───────────────────────
+
+impl From<&content::Project> for Project {
+    fn from(p: &content::Project) -> Self {
+        Self {
+            name: p.name.to_string(),
+            description: p.description.to_string(),
+            tech: p.tech.to_string(),
+            url: p.url.to_string(),
+        }
+    }
+}
+
+impl From<&content::ProjectCategory> for ProjectCategory {
+    fn from(c: &content::ProjectCategory) -> Self {
+        Self {
+            name: c.name.to_string(),
+            projects: c.projects.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&content::SkillGroup> for SkillGroup {
+    fn from(g: &content::SkillGroup) -> Self {
+        Self {
+            name: g.name.to_string(),
+            items: g.items.iter().map(|i| i.to_string()).collect(),
+        }
+    }
+}
+
+impl From<&content::ContactEntry> for ContactEntry {
+    fn from(e: &content::ContactEntry) -> Self {
+        Self {
+            label: e.label.to_string(),
+            value: e.value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_are_read_in_order_by_kind() {
+        let portfolio = Portfolio::parse(
+            r#"
+            [[tabs]]
+            kind = "key_value"
+            title = "Contact"
+            entries = [{ label = "Email", value = "me@example.com" }]
+
+            [[tabs]]
+            kind = "text"
+            title = "About"
+            body = "Hello"
+            "#,
+        )
+        .unwrap();
+        let titles: Vec<&str> = portfolio.tabs.iter().map(Tab::title).collect();
+        assert_eq!(titles, ["Contact", "About"]);
+        assert!(matches!(&portfolio.tabs[0], Tab::KeyValue { outro, .. } if outro.is_empty()));
+    }
+
+    #[test]
+    fn unknown_tab_kinds_are_rejected() {
+        let raw = "[[tabs]]\nkind = \"gallery\"\ntitle = \"Photos\"\n";
+        assert!(Portfolio::parse(raw).is_err());
+    }
+
+    #[test]
+    fn load_picks_up_edits_and_reports_broken_files() {
+        let dir = std::env::temp_dir().join(format!("portfolio-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("portfolio.toml");
+
+        std::fs::write(&path, "banner = [\"one\"]\n").unwrap();
+        let first = Portfolio::load(&path).map(|p| p.banner);
+        std::fs::write(&path, "banner = [\"two\"]\n").unwrap();
+        let second = Portfolio::load(&path).map(|p| p.banner);
+        std::fs::write(&path, "banner = [\"unterminated\n").unwrap();
+        let broken = Portfolio::load(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.unwrap(), ["one"]);
+        assert_eq!(second.unwrap(), ["two"]);
+        let error = format!("{:#}", broken.unwrap_err());
+        assert!(error.starts_with("failed to parse"), "{error}");
+    }
+
+    #[test]
+    fn replacing_the_shared_portfolio_leaves_old_handles_intact() {
+        let shared = SharedPortfolio::new(Portfolio::builtin());
+        let old = shared.current();
+        shared.replace(Portfolio::parse("tabs = []").unwrap());
+
+        assert!(shared.current().tabs.is_empty());
+        assert!(!old.tabs.is_empty());
+    }
+
+    fn categories() -> Vec<ProjectCategory> {
+        let raw = r#"
+            [[tabs]]
+            kind = "projects"
+            title = "Projects"
+
+            [[tabs.categories]]
+            name = "Apps"
+            projects = [
+              { name = "a", description = "", tech = "Rust, Ratatui", url = "" },
+              { name = "b", description = "", tech = "Go", url = "" },
+            ]
+
+            [[tabs.categories]]
+            name = "Tools"
+            projects = [
+              { name = "c", description = "", tech = "rust, go", url = "" },
+            ]
+            "#;
+        match Portfolio::parse(raw).unwrap().tabs.remove(0) {
+            Tab::Projects { categories, .. } => categories,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn tags_are_merged_case_insensitively() {
+        assert_eq!(all_tags(&categories()), ["Go", "Ratatui", "Rust"]);
+    }
+
+    #[test]
+    fn filters_skip_categories_without_a_match() {
+        let categories = categories();
+        assert_eq!(total_project_count(&categories, None), 3);
+        assert_eq!(total_project_count(&categories, Some("RUST")), 2);
+        assert_eq!(total_project_lines(&categories, Some("ratatui")), 2 + 4);
+        let (cat, project) = get_project_by_flat_index(&categories, 1, Some("go")).unwrap();
+        assert_eq!((cat.name.as_str(), project.name.as_str()), ("Tools", "c"));
+        assert!(get_project_by_flat_index(&categories, 2, Some("go")).is_none());
+    }
+
+    #[test]
+    fn remapping_follows_the_project_between_filters() {
+        let categories = categories();
+        assert_eq!(
+            remap_project_index(&categories, 2, None, Some("go")),
+            Some(1)
+        );
+        assert_eq!(
+            remap_project_index(&categories, 1, Some("go"), Some("rust")),
+            Some(1)
+        );
+        assert_eq!(
+            remap_project_index(&categories, 0, Some("rust"), None),
+            Some(0)
+        );
+        assert_eq!(
+            remap_project_index(&categories, 1, None, Some("rust")),
+            None
+        );
+        assert_eq!(remap_project_index(&categories, 5, None, None), None);
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+
+// ── Blog posts ─────────────────────────────────────────────────
+//
+// Posts are Markdown files with a small front matter block:
+//
+//     ---
+//     title: Writing a TUI over SSH
+//     date: 2024-05-01
+//     tags: [rust, ratatui]
+//     ---
+//
+// Only flat `key: value` pairs are understood; `tags` may be a
+// `[a, b]` list or a comma-separated string.
+
+#[derive(Debug, Clone)]
+pub struct Post {
+    pub title: String,
+    /// Publication date as `YYYY-MM-DD`, so sorting the strings sorts by date.
+    pub date: String,
+    pub tags: Vec<String>,
+    /// Post body, in Markdown.
+    pub body: String,
+    /// File the post was read from.
+    pub path: PathBuf,
+}
+
+/// Read every `*.md` file in `dir`, newest first.
+pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Post>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+
+    let mut posts = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "md") {
+            posts.push(load(&path)?);
+        }
+    }
+
+    // Newest first; ties broken by title for a stable order
+    posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.title.cmp(&b.title)));
+    Ok(posts)
+}
+
+/// Markdown files in `dir`, for change detection. Errors are ignored.
+pub fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn load(path: &Path) -> anyhow::Result<Post> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    parse(&raw, path).with_context(|| format!("invalid post {}", path.display()))
+}
+
+fn parse(raw: &str, path: &Path) -> anyhow::Result<Post> {
+    let Some(rest) = raw.strip_prefix("---\n").or_else(|| raw.strip_prefix("---\r\n")) else {
+        bail!("missing front matter (the file must start with a `---` line)");
+    };
+    let Some((front, body)) = rest
+        .split_once("\n---\n")
+        .or_else(|| rest.split_once("\r\n---\r\n"))
+        .or_else(|| rest.strip_suffix("\n---").map(|f| (f, "")))
+    else {
+        bail!("unterminated front matter (expected a closing `---` line)");
+    };
+
+    let mut title = None;
+    let mut date = None;
+    let mut tags = Vec::new();
+
+    for (n, line) in front.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            bail!("front matter line {}: expected `key: value`", n + 2);
+        };
+        let value = unquote(value.trim());
+        match key.trim() {
+            "title" => title = Some(value.to_string()),
+            "date" => date = Some(value.to_string()),
+            "tags" => {
+                tags = value
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|t| unquote(t.trim()).to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+            }
+            // Unknown keys are allowed so posts can carry extra metadata
+            _ => {}
+        }
+    }
+
+    let Some(title) = title.filter(|t| !t.is_empty()) else {
+        bail!("front matter has no `title`");
+    };
+    let Some(date) = date else {
+        bail!("front matter has no `date`");
+    };
+    if !is_iso_date(&date) {
+        bail!("date {date:?} is not in YYYY-MM-DD format");
+    }
+
+    Ok(Post {
+        title,
+        date,
+        tags,
+        body: body.trim_start_matches(['\r', '\n']).to_string(),
+        path: path.to_path_buf(),
+    })
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+fn is_iso_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    matches!(parts.as_slice(), [y, m, d]
+        if y.len() == 4 && m.len() == 2 && d.len() == 2
+            && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(raw: &str) -> anyhow::Result<Post> {
+        parse(raw, Path::new("post.md"))
+    }
+
+    #[test]
+    fn front_matter_fields_are_read() {
+        let post = post(
+            "---\ntitle: \"Hello: world\"\ndate: 2024-05-01\ntags: [rust, 'tui']\nlayout: post\n---\n\n# Body\n",
+        )
+        .unwrap();
+        assert_eq!(post.title, "Hello: world");
+        assert_eq!(post.date, "2024-05-01");
+        assert_eq!(post.tags, ["rust", "tui"]);
+        assert_eq!(post.body, "# Body\n");
+    }
+
+    #[test]
+    fn tags_may_be_a_comma_separated_string() {
+        let post = post("---\ntitle: A\ndate: 2024-01-02\ntags: rust, ssh,\n---\n").unwrap();
+        assert_eq!(post.tags, ["rust", "ssh"]);
+        assert_eq!(post.body, "");
+    }
+
+    #[test]
+    fn crlf_line_endings_are_accepted() {
+        let post = post("---\r\ntitle: A\r\ndate: 2024-01-02\r\n---\r\nBody").unwrap();
+        assert_eq!(post.title, "A");
+        assert_eq!(post.body, "Body");
+    }
+
+    #[test]
+    fn malformed_front_matter_is_rejected() {
+        assert!(post("title: A\n").is_err());
+        assert!(post("---\ntitle: A\ndate: 2024-01-02\n").is_err());
+        assert!(post("---\ntitle A\ndate: 2024-01-02\n---\n").is_err());
+        assert!(post("---\ndate: 2024-01-02\n---\n").is_err());
+        assert!(post("---\ntitle: A\n---\n").is_err());
+        assert!(post("---\ntitle: A\ndate: 1 May 2024\n---\n").is_err());
+    }
+}
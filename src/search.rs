@@ -0,0 +1,287 @@
+use crate::markdown;
+use crate::portfolio::{Portfolio, Tab};
+use crate::theme::Theme;
+
+// ── Fuzzy search ───────────────────────────────────────────────
+//
+// Every searchable piece of text in the portfolio becomes a candidate
+// that knows where it lives. Candidates are fuzzy-matched against the
+// query (the query's characters must appear in order, not necessarily
+// next to each other) and ranked by how tight the match is.
+
+/// Maximum number of results kept for a query.
+const MAX_RESULTS: usize = 50;
+
+/// Where a search result leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A project, by flat index within its tab.
+    Project(usize),
+    /// A timeline entry.
+    Role(usize),
+    /// A post in a posts tab.
+    Post(usize),
+    /// A rendered line of a text tab, used as the scroll offset.
+    Line(usize),
+    /// The tab itself.
+    Tab,
+}
+
+/// A piece of searchable text and where it comes from.
+struct Candidate {
+    tab: usize,
+    target: Target,
+    text: String,
+    /// Where the text was found, e.g. "Projects › miru".
+    context: String,
+}
+
+/// A ranked search result.
+#[derive(Debug, Clone)]
+pub struct Hit {
+    pub tab: usize,
+    pub target: Target,
+    pub text: String,
+    pub context: String,
+    /// Indices (in chars) of `text` matched by the query, for highlighting.
+    pub matched: Vec<usize>,
+    score: i64,
+}
+
+/// State of the search prompt while it is open.
+#[derive(Debug, Clone, Default)]
+pub struct SearchState {
+    pub query: String,
+    pub results: Vec<Hit>,
+    /// Index of the highlighted result.
+    pub selected: usize,
+}
+
+impl SearchState {
+    /// Re-run the query against `portfolio`. Text tabs are searched line
+    /// by line as rendered at `width`, so a hit maps to a scroll offset.
+    pub fn refresh(&mut self, portfolio: &Portfolio, width: usize) {
+        self.results = search(portfolio, &self.query, width);
+        self.selected = 0;
+    }
+
+    pub fn selected_hit(&self) -> Option<&Hit> {
+        self.results.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Rank every candidate in `portfolio` against `query`, best first.
+pub fn search(portfolio: &Portfolio, query: &str, width: usize) -> Vec<Hit> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<Hit> = candidates(portfolio, width)
+        .into_iter()
+        .filter_map(|c| {
+            let (score, matched) = fuzzy_match(&query, &c.text)?;
+            Some(Hit {
+                tab: c.tab,
+                target: c.target,
+                text: c.text,
+                context: c.context,
+                matched,
+                score,
+            })
+        })
+        .collect();
+
+    // Stable sort keeps content order among equal scores
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+    hits.truncate(MAX_RESULTS);
+    hits
+}
+
+fn candidates(portfolio: &Portfolio, width: usize) -> Vec<Candidate> {
+    let mut out = Vec::new();
+    let mut push = |tab: usize, target: Target, text: &str, context: String| {
+        let text = text.trim();
+        if !text.is_empty() {
+            out.push(Candidate {
+                tab,
+                target,
+                text: text.to_string(),
+                context,
+            });
+        }
+    };
+
+    for (t, tab) in portfolio.tabs.iter().enumerate() {
+        let title = tab.title();
+        match tab {
+            Tab::Text { body, .. } => {
+                for (n, line) in markdown::render(body, width, &Theme::default())
+                    .iter()
+                    .enumerate()
+                {
+                    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                    push(t, Target::Line(n), &text, title.to_string());
+                }
+            }
+            Tab::Projects { categories, .. } => {
+                let projects = categories.iter().flat_map(|c| &c.projects);
+                for (i, project) in projects.enumerate() {
+                    let context = format!("{title} \u{203a} {}", project.name);
+                    push(t, Target::Project(i), &project.name, title.to_string());
+                    // Descriptions are Markdown; search them as one line of prose
+                    let description = project.description.split_whitespace().collect::<Vec<_>>();
+                    push(
+                        t,
+                        Target::Project(i),
+                        &description.join(" "),
+                        context.clone(),
+                    );
+                    push(t, Target::Project(i), &project.tech, context.clone());
+                }
+            }
+            Tab::Timeline { entries, .. } => {
+                for (i, role) in entries.iter().enumerate() {
+                    let text = format!("{} \u{00b7} {}", role.title, role.client);
+                    push(t, Target::Role(i), &text, title.to_string());
+                }
+            }
+            Tab::Posts { posts, .. } => {
+                for (i, post) in posts.iter().enumerate() {
+                    push(t, Target::Post(i), &post.title, title.to_string());
+                }
+            }
+            Tab::Skills { groups, .. } => {
+                for group in groups {
+                    for item in &group.items {
+                        push(
+                            t,
+                            Target::Tab,
+                            item,
+                            format!("{title} \u{203a} {}", group.name),
+                        );
+                    }
+                }
+            }
+            Tab::KeyValue { entries, .. } => {
+                for entry in entries {
+                    push(
+                        t,
+                        Target::Tab,
+                        &entry.value,
+                        format!("{title} \u{203a} {}", entry.label),
+                    );
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Match the (lowercased) `query` against `text` as a subsequence.
+///
+/// Returns a score, higher being better, and the matched char indices.
+/// Consecutive matches and matches at the start of a word score higher;
+/// gaps between matched characters cost a little.
+fn fuzzy_match(query: &[char], text: &str) -> Option<(i64, Vec<usize>)> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    // Prefer a contiguous occurrence when there is one, so "rust" in a
+    // long description highlights the word rather than scattered letters.
+    let start = find_run(&lower, query).unwrap_or(0);
+
+    let mut matched = Vec::with_capacity(query.len());
+    let mut q = 0;
+    for (i, c) in lower.iter().enumerate().skip(start) {
+        if q < query.len() && *c == query[q] {
+            matched.push(i);
+            q += 1;
+        }
+    }
+    if q < query.len() {
+        return None;
+    }
+
+    let mut score: i64 = 0;
+    let mut prev: Option<usize> = None;
+    for &i in &matched {
+        score += 10;
+        let word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+        if word_start {
+            score += 8;
+        }
+        match prev {
+            Some(p) if p + 1 == i => score += 12,
+            Some(p) => score -= (i - p - 1).min(10) as i64,
+            None => score -= i.min(10) as i64,
+        }
+        prev = Some(i);
+    }
+    // Shorter texts are a more specific match
+    score -= (chars.len() / 16) as i64;
+
+    Some((score, matched))
+}
+
+/// Index of the first contiguous occurrence of `needle` in `haystack`.
+fn find_run(haystack: &[char], needle: &[char]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    fn score(q: &str, text: &str) -> i64 {
+        fuzzy_match(&query(q), text).map_or(i64::MIN, |(score, _)| score)
+    }
+
+    #[test]
+    fn matches_a_subsequence_ignoring_case() {
+        let (_, matched) = fuzzy_match(&query("rs"), "RuSt").unwrap();
+        assert_eq!(matched, [0, 2]);
+        assert!(fuzzy_match(&query("sr"), "rust").is_none());
+        assert!(fuzzy_match(&query("rusty"), "rust").is_none());
+    }
+
+    #[test]
+    fn prefers_a_contiguous_occurrence() {
+        let (_, matched) = fuzzy_match(&query("rust"), "react uses typescript, rust").unwrap();
+        assert_eq!(matched, [23, 24, 25, 26]);
+    }
+
+    #[test]
+    fn word_starts_and_runs_score_higher() {
+        assert!(score("ts", "TypeScript") < score("ts", "ts-node"));
+        assert!(score("go", "gecko") < score("go", "golang"));
+        assert!(score("nord", "the nord theme") > score("nord", "n o r d"));
+    }
+
+    #[test]
+    fn shorter_texts_score_higher() {
+        let long = format!("rust {}", "x".repeat(64));
+        assert!(score("rust", "rust") > score("rust", &long));
+    }
+}
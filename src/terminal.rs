@@ -8,20 +8,35 @@ use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 /// sequences into a buffer, which is then flushed over the SSH channel
 /// via a tokio mpsc channel.
 pub struct TerminalHandle {
-    sender: UnboundedSender<Vec<u8>>,
+    sender: UnboundedSender<Output>,
     sink: Vec<u8>,
 }
 
+/// What the background task forwards to the SSH channel, in order.
+enum Output {
+    Data(Vec<u8>),
+    /// Close the channel, after everything sent before.
+    Close,
+}
+
 impl TerminalHandle {
     /// Spawn a background task that forwards buffered writes to the SSH channel.
     pub async fn start(handle: Handle, channel_id: ChannelId) -> Self {
-        let (sender, mut receiver) = unbounded_channel::<Vec<u8>>();
+        let (sender, mut receiver) = unbounded_channel::<Output>();
 
         tokio::spawn(async move {
-            while let Some(data) = receiver.recv().await {
-                if let Err(e) = handle.data(channel_id, data.into()).await {
-                    log::error!("Failed to send data to SSH channel: {e:?}");
-                    break;
+            while let Some(output) = receiver.recv().await {
+                match output {
+                    Output::Data(data) => {
+                        if let Err(e) = handle.data(channel_id, data.into()).await {
+                            log::error!("Failed to send data to SSH channel: {e:?}");
+                            break;
+                        }
+                    }
+                    Output::Close => {
+                        let _ = handle.close(channel_id).await;
+                        break;
+                    }
                 }
             }
         });
@@ -31,6 +46,21 @@ impl TerminalHandle {
             sink: Vec::new(),
         }
     }
+
+    /// A way to close the SSH channel from outside the terminal.
+    pub fn closer(&self) -> Closer {
+        Closer(self.sender.clone())
+    }
+}
+
+/// Closes the SSH channel behind a [`TerminalHandle`].
+pub struct Closer(UnboundedSender<Output>);
+
+impl Closer {
+    /// Close the channel once everything flushed so far has been sent.
+    pub fn close(&self) {
+        let _ = self.0.send(Output::Close);
+    }
 }
 
 impl std::io::Write for TerminalHandle {
@@ -40,7 +70,7 @@ impl std::io::Write for TerminalHandle {
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        self.sender.send(self.sink.clone()).map_err(|e| {
+        self.sender.send(Output::Data(self.sink.clone())).map_err(|e| {
             std::io::Error::new(std::io::ErrorKind::BrokenPipe, e)
         })?;
         self.sink.clear();
@@ -1,62 +1,453 @@
+use std::borrow::Cow;
+
 use ratatui::style::{Color, Modifier, Style};
 
-// ── Header ─────────────────────────────────────────────────────
-pub const HEADER: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
-// ── Tabs ───────────────────────────────────────────────────────
-pub const TAB_ACTIVE: Style = Style::new()
-    .fg(Color::White)
-    .add_modifier(Modifier::BOLD)
-    .add_modifier(Modifier::UNDERLINED);
-pub const TAB_INACTIVE: Style = Style::new().fg(Color::DarkGray);
-pub const TAB_NUMBER: Style = Style::new().fg(Color::DarkGray);
-
-// ── Borders ────────────────────────────────────────────────────
-pub const BORDER: Style = Style::new().fg(Color::DarkGray);
-
-// ── Text ───────────────────────────────────────────────────────
-pub const TEXT: Style = Style::new().fg(Color::White);
-pub const TEXT_DIM: Style = Style::new().fg(Color::Gray);
-pub const TEXT_MUTED: Style = Style::new().fg(Color::DarkGray);
-pub const TEXT_BOLD: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
-
-// ── Links ──────────────────────────────────────────────────────
-pub const LINK: Style = Style::new()
-    .fg(Color::Blue)
-    .add_modifier(Modifier::UNDERLINED);
-
-// ── Projects ───────────────────────────────────────────────────
-pub const CATEGORY_HEADER: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-/// Style for the selected project name in the left pane list.
-pub const PROJECT_SELECTED: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-/// Style for unselected project names in the left pane list.
-pub const PROJECT_LIST_ITEM: Style = Style::new().fg(Color::White);
-/// Style for the selection indicator arrow.
-pub const PROJECT_ARROW: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-/// Style for the project name in the detail pane.
-pub const PROJECT_DETAIL_NAME: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
-/// Style for labels in the detail pane (e.g. "Tech:", "URL:").
-pub const PROJECT_DETAIL_LABEL: Style = Style::new()
-    .fg(Color::DarkGray)
-    .add_modifier(Modifier::BOLD);
-/// Style for the category name shown in the detail pane.
-pub const PROJECT_DETAIL_CATEGORY: Style = Style::new().fg(Color::Cyan);
-
-// ── Skills ─────────────────────────────────────────────────────
-pub const SKILL_GROUP: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-pub const SKILL_ITEM: Style = Style::new().fg(Color::White);
-
-// ── Contact ────────────────────────────────────────────────────
-pub const CONTACT_LABEL: Style = Style::new()
-    .fg(Color::DarkGray)
-    .add_modifier(Modifier::BOLD);
-// ── Footer ─────────────────────────────────────────────────────
-pub const KEY_HINT: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
-pub const KEY_ACTION: Style = Style::new().fg(Color::DarkGray);
-
-// ── Scroll indicator ───────────────────────────────────────────
-pub const SCROLL_INDICATOR: Style = Style::new().fg(Color::DarkGray);
-
-// ── Intro animation ────────────────────────────────────────────
-pub const INTRO_CURSOR: Style = Style::new()
-    .fg(Color::White)
-    .add_modifier(Modifier::RAPID_BLINK);
+use crate::caps::ColorDepth;
+
+// ── Palettes ───────────────────────────────────────────────────
+//
+// A palette names the handful of colours the UI is drawn with; a theme
+// derives every style from one. Each session picks a colour scheme, one
+// of those below or one loaded from a Base16 file (`t` cycles through
+// them, `:theme` names one), and gets the theme that suits its
+// terminal: the scheme's RGB palette on truecolor terminals, the same
+// palette mapped to the nearest 256- or 16-colour entries, or no colours
+// at all, with selection and emphasis left to bold, underline and
+// reverse video.
+
+/// The colours a theme is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Behind everything; `Reset` leaves the terminal's own.
+    pub background: Color,
+    /// Body text and anything that should stand out from it by weight.
+    pub text: Color,
+    /// Secondary text, like dates and code blocks.
+    pub text_dim: Color,
+    /// Hints, labels and separators.
+    pub text_muted: Color,
+    pub border: Color,
+    /// Headings, selections and prompts.
+    pub accent: Color,
+    pub link: Color,
+    /// Matches, active filters and inline code.
+    pub highlight: Color,
+    pub success: Color,
+    pub error: Color,
+}
+
+impl Palette {
+    /// The named ANSI colours, which follow the terminal's own scheme.
+    pub const ANSI: Palette = Palette {
+        background: Color::Reset,
+        text: Color::White,
+        text_dim: Color::Gray,
+        text_muted: Color::DarkGray,
+        border: Color::DarkGray,
+        accent: Color::Cyan,
+        link: Color::Blue,
+        highlight: Color::Yellow,
+        success: Color::Green,
+        error: Color::Red,
+    };
+
+    /// Softer RGB colours along the same lines, over the terminal's own
+    /// background.
+    pub const RGB: Palette = Palette {
+        background: Color::Reset,
+        text: Color::Rgb(205, 214, 244),
+        text_dim: Color::Rgb(166, 173, 200),
+        text_muted: Color::Rgb(108, 112, 134),
+        border: Color::Rgb(88, 91, 112),
+        accent: Color::Rgb(137, 220, 235),
+        link: Color::Rgb(137, 180, 250),
+        highlight: Color::Rgb(249, 226, 175),
+        success: Color::Rgb(166, 227, 161),
+        error: Color::Rgb(243, 139, 168),
+    };
+
+    /// No colours: everything in the terminal's default foreground.
+    const MONO: Palette = Palette {
+        background: Color::Reset,
+        text: Color::Reset,
+        text_dim: Color::Reset,
+        text_muted: Color::Reset,
+        border: Color::Reset,
+        accent: Color::Reset,
+        link: Color::Reset,
+        highlight: Color::Reset,
+        success: Color::Reset,
+        error: Color::Reset,
+    };
+
+    /// The palette with each colour replaced by the closest `depth` has.
+    pub fn adapt(self, depth: ColorDepth) -> Palette {
+        Palette {
+            background: depth.adapt(self.background),
+            text: depth.adapt(self.text),
+            text_dim: depth.adapt(self.text_dim),
+            text_muted: depth.adapt(self.text_muted),
+            border: depth.adapt(self.border),
+            accent: depth.adapt(self.accent),
+            link: depth.adapt(self.link),
+            highlight: depth.adapt(self.highlight),
+            success: depth.adapt(self.success),
+            error: depth.adapt(self.error),
+        }
+    }
+}
+
+// ── Schemes ────────────────────────────────────────────────────
+
+/// A named colour scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    pub name: Cow<'static, str>,
+    /// Colours for terminals with 256 colours or more.
+    rgb: Palette,
+    /// Colours for 16-colour terminals, if not the closest to `rgb`.
+    ansi: Option<Palette>,
+}
+
+/// Schemes that come with the server, in the order `t` cycles through
+/// them, ahead of any loaded from Base16 files. The first is the default.
+pub const BUNDLED: &[Scheme] = &[
+    Scheme {
+        name: Cow::Borrowed("default"),
+        rgb: Palette::RGB,
+        ansi: Some(Palette::ANSI),
+    },
+    Scheme {
+        name: Cow::Borrowed("light"),
+        rgb: Palette {
+            background: Color::Rgb(250, 250, 250),
+            text: Color::Rgb(36, 41, 47),
+            text_dim: Color::Rgb(87, 96, 106),
+            text_muted: Color::Rgb(140, 149, 159),
+            border: Color::Rgb(208, 215, 222),
+            accent: Color::Rgb(130, 80, 223),
+            link: Color::Rgb(9, 105, 218),
+            highlight: Color::Rgb(154, 103, 0),
+            success: Color::Rgb(26, 127, 55),
+            error: Color::Rgb(207, 34, 46),
+        },
+        ansi: None,
+    },
+    Scheme {
+        name: Cow::Borrowed("dracula"),
+        rgb: Palette {
+            background: Color::Rgb(40, 42, 54),
+            text: Color::Rgb(248, 248, 242),
+            text_dim: Color::Rgb(200, 200, 195),
+            text_muted: Color::Rgb(98, 114, 164),
+            border: Color::Rgb(68, 71, 90),
+            accent: Color::Rgb(189, 147, 249),
+            link: Color::Rgb(139, 233, 253),
+            highlight: Color::Rgb(241, 250, 140),
+            success: Color::Rgb(80, 250, 123),
+            error: Color::Rgb(255, 85, 85),
+        },
+        ansi: None,
+    },
+    Scheme {
+        name: Cow::Borrowed("gruvbox"),
+        rgb: Palette {
+            background: Color::Rgb(40, 40, 40),
+            text: Color::Rgb(235, 219, 178),
+            text_dim: Color::Rgb(213, 196, 161),
+            text_muted: Color::Rgb(146, 131, 116),
+            border: Color::Rgb(80, 73, 69),
+            accent: Color::Rgb(254, 128, 25),
+            link: Color::Rgb(131, 165, 152),
+            highlight: Color::Rgb(250, 189, 47),
+            success: Color::Rgb(184, 187, 38),
+            error: Color::Rgb(251, 73, 52),
+        },
+        ansi: None,
+    },
+    Scheme {
+        name: Cow::Borrowed("catppuccin"),
+        rgb: Palette {
+            background: Color::Rgb(30, 30, 46),
+            text: Color::Rgb(205, 214, 244),
+            text_dim: Color::Rgb(186, 194, 222),
+            text_muted: Color::Rgb(108, 112, 134),
+            border: Color::Rgb(88, 91, 112),
+            accent: Color::Rgb(203, 166, 247),
+            link: Color::Rgb(137, 180, 250),
+            highlight: Color::Rgb(249, 226, 175),
+            success: Color::Rgb(166, 227, 161),
+            error: Color::Rgb(243, 139, 168),
+        },
+        ansi: None,
+    },
+    Scheme {
+        name: Cow::Borrowed("nord"),
+        rgb: Palette {
+            background: Color::Rgb(46, 52, 64),
+            text: Color::Rgb(236, 239, 244),
+            text_dim: Color::Rgb(216, 222, 233),
+            text_muted: Color::Rgb(123, 136, 161),
+            border: Color::Rgb(67, 76, 94),
+            accent: Color::Rgb(136, 192, 208),
+            link: Color::Rgb(129, 161, 193),
+            highlight: Color::Rgb(235, 203, 139),
+            success: Color::Rgb(163, 190, 140),
+            error: Color::Rgb(191, 97, 106),
+        },
+        ansi: None,
+    },
+    Scheme {
+        name: Cow::Borrowed("high-contrast"),
+        rgb: Palette {
+            background: Color::Rgb(0, 0, 0),
+            text: Color::Rgb(255, 255, 255),
+            text_dim: Color::Rgb(255, 255, 255),
+            text_muted: Color::Rgb(204, 204, 204),
+            border: Color::Rgb(255, 255, 255),
+            accent: Color::Rgb(255, 215, 0),
+            link: Color::Rgb(0, 255, 255),
+            highlight: Color::Rgb(255, 0, 255),
+            success: Color::Rgb(0, 255, 0),
+            error: Color::Rgb(255, 85, 85),
+        },
+        ansi: None,
+    },
+];
+
+impl Scheme {
+    /// A scheme drawn in `rgb`, and in the closest colours to it on
+    /// 16-colour terminals.
+    pub fn new(name: String, rgb: Palette) -> Self {
+        Self {
+            name: Cow::Owned(name),
+            rgb,
+            ansi: None,
+        }
+    }
+}
+
+/// Index of the scheme called `name` in `schemes`, ignoring case.
+pub fn find(schemes: &[Scheme], name: &str) -> Option<usize> {
+    schemes
+        .iter()
+        .position(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+// ── Theme ──────────────────────────────────────────────────────
+
+/// Every style the UI draws with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Behind everything, and behind popups.
+    pub background: Style,
+    // Header
+    pub header: Style,
+    // Tabs
+    pub tab_active: Style,
+    pub tab_inactive: Style,
+    pub tab_number: Style,
+    // Borders
+    pub border: Style,
+    // Text
+    pub text: Style,
+    pub text_dim: Style,
+    pub text_muted: Style,
+    pub text_bold: Style,
+    // Links
+    pub link: Style,
+    // Projects
+    pub category_header: Style,
+    /// The selected project name in the left pane list.
+    pub project_selected: Style,
+    /// Unselected project names in the left pane list.
+    pub project_list_item: Style,
+    /// The selection indicator arrow.
+    pub project_arrow: Style,
+    /// The project name in the detail pane.
+    pub project_detail_name: Style,
+    /// Labels in the detail pane (e.g. "Tech:", "URL:").
+    pub project_detail_label: Style,
+    /// The category name shown in the detail pane.
+    pub project_detail_category: Style,
+    /// The active tag filter.
+    pub filter_tag: Style,
+    // Timeline
+    /// The dot of the selected entry.
+    pub timeline_dot_selected: Style,
+    pub timeline_dot: Style,
+    /// The vertical line joining entries.
+    pub timeline_connector: Style,
+    /// The client and date range under each role.
+    pub timeline_meta: Style,
+    // Skills
+    pub skill_group: Style,
+    pub skill_item: Style,
+    // Contact
+    pub contact_label: Style,
+    // Markdown
+    /// Level 2+ headings.
+    pub md_subheading: Style,
+    pub md_code: Style,
+    pub md_code_block: Style,
+    /// List bullets and numbers.
+    pub md_bullet: Style,
+    /// The bar in front of block quotes.
+    pub md_quote: Style,
+    // Search
+    pub search_prompt: Style,
+    /// The characters of a result matched by the query.
+    pub search_match: Style,
+    /// Where a result was found, e.g. "Projects › miru".
+    pub search_context: Style,
+    // Command palette
+    pub command_prompt: Style,
+    /// Completions the prompt could not choose between.
+    pub command_candidate: Style,
+    pub command_output: Style,
+    pub command_error: Style,
+    // Contact card
+    /// The QR code, in fixed colours so it scans the same on any
+    /// terminal background: light modules are drawn in the foreground.
+    pub qr_code: Style,
+    // Toast
+    pub toast: Style,
+    // Footer
+    pub key_hint: Style,
+    pub key_action: Style,
+    // Scroll indicator
+    pub scroll_indicator: Style,
+    // Intro animation
+    pub intro_cursor: Style,
+}
+
+impl Default for Theme {
+    /// The ANSI theme, for output where colours do not matter.
+    fn default() -> Self {
+        Self::from_palette(Palette::ANSI)
+    }
+}
+
+impl Theme {
+    /// `scheme` as shown by a terminal showing `depth` colours.
+    pub fn new(scheme: &Scheme, depth: ColorDepth) -> Self {
+        match depth {
+            ColorDepth::TrueColor => Self::from_palette(scheme.rgb),
+            ColorDepth::Ansi256 => Self::from_palette(scheme.rgb.adapt(depth)),
+            ColorDepth::Ansi16 => {
+                Self::from_palette(scheme.ansi.unwrap_or_else(|| scheme.rgb.adapt(depth)))
+            }
+            ColorDepth::Mono => Self::mono(),
+        }
+    }
+
+    /// Styles drawn in the colours of `p`.
+    pub fn from_palette(p: Palette) -> Self {
+        let bold = Modifier::BOLD;
+        Self {
+            background: Style::new().bg(p.background),
+            header: Style::new().fg(p.text).add_modifier(bold),
+            tab_active: Style::new()
+                .fg(p.text)
+                .add_modifier(bold)
+                .add_modifier(Modifier::UNDERLINED),
+            tab_inactive: Style::new().fg(p.text_muted),
+            tab_number: Style::new().fg(p.text_muted),
+            border: Style::new().fg(p.border),
+            text: Style::new().fg(p.text),
+            text_dim: Style::new().fg(p.text_dim),
+            text_muted: Style::new().fg(p.text_muted),
+            text_bold: Style::new().fg(p.text).add_modifier(bold),
+            link: Style::new().fg(p.link).add_modifier(Modifier::UNDERLINED),
+            category_header: Style::new().fg(p.accent).add_modifier(bold),
+            project_selected: Style::new().fg(p.accent).add_modifier(bold),
+            project_list_item: Style::new().fg(p.text),
+            project_arrow: Style::new().fg(p.accent).add_modifier(bold),
+            project_detail_name: Style::new().fg(p.text).add_modifier(bold),
+            project_detail_label: Style::new().fg(p.text_muted).add_modifier(bold),
+            project_detail_category: Style::new().fg(p.accent),
+            filter_tag: Style::new().fg(p.highlight).add_modifier(bold),
+            timeline_dot_selected: Style::new().fg(p.accent).add_modifier(bold),
+            timeline_dot: Style::new().fg(p.text_muted),
+            timeline_connector: Style::new().fg(p.text_muted),
+            timeline_meta: Style::new().fg(p.text_dim),
+            skill_group: Style::new().fg(p.accent).add_modifier(bold),
+            skill_item: Style::new().fg(p.text),
+            contact_label: Style::new().fg(p.text_muted).add_modifier(bold),
+            md_subheading: Style::new().fg(p.accent).add_modifier(bold),
+            md_code: Style::new().fg(p.highlight),
+            md_code_block: Style::new().fg(p.text_dim),
+            md_bullet: Style::new().fg(p.accent),
+            md_quote: Style::new().fg(p.text_muted),
+            search_prompt: Style::new().fg(p.accent).add_modifier(bold),
+            search_match: Style::new().fg(p.highlight).add_modifier(bold),
+            search_context: Style::new().fg(p.text_muted),
+            command_prompt: Style::new().fg(p.accent).add_modifier(bold),
+            command_candidate: Style::new().fg(p.text_muted),
+            command_output: Style::new().fg(p.text),
+            command_error: Style::new().fg(p.error),
+            qr_code: Style::new().fg(Color::White).bg(Color::Black),
+            toast: Style::new().fg(p.success),
+            key_hint: Style::new().fg(p.text).add_modifier(bold),
+            key_action: Style::new().fg(p.text_muted),
+            scroll_indicator: Style::new().fg(p.text_muted),
+            intro_cursor: Style::new().fg(p.text).add_modifier(Modifier::RAPID_BLINK),
+        }
+    }
+
+    /// No colours, for `TERM=dumb`-like terminals and `NO_COLOR`. What
+    /// colour alone set apart is marked with reverse video or underline.
+    fn mono() -> Self {
+        let reversed = Modifier::BOLD | Modifier::REVERSED;
+        let underlined = Modifier::BOLD | Modifier::UNDERLINED;
+        let theme = Self::from_palette(Palette::MONO);
+        Self {
+            project_selected: theme.project_selected.add_modifier(reversed),
+            filter_tag: theme.filter_tag.add_modifier(underlined),
+            md_code: theme.md_code.add_modifier(Modifier::BOLD),
+            search_match: theme.search_match.add_modifier(underlined),
+            // Underline alone is taken by the active tab, and links are
+            // found on screen by their style
+            link: theme.link.add_modifier(Modifier::ITALIC),
+            command_error: theme.command_error.add_modifier(Modifier::BOLD),
+            // Whatever the terminal's colours are, light modules are
+            // drawn in its foreground
+            qr_code: Style::new(),
+            toast: theme.toast.add_modifier(Modifier::BOLD),
+            ..theme
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_schemes_are_found_by_name() {
+        assert_eq!(find(BUNDLED, "default"), Some(0));
+        assert_eq!(find(BUNDLED, "High-Contrast"), Some(BUNDLED.len() - 1));
+        assert_eq!(find(BUNDLED, "solarized"), None);
+        for (i, s) in BUNDLED.iter().enumerate() {
+            assert_eq!(find(BUNDLED, &s.name), Some(i), "{} is shadowed", s.name);
+        }
+    }
+
+    #[test]
+    fn themes_follow_the_colour_depth() {
+        let light = &BUNDLED[1];
+        assert_eq!(
+            Theme::new(light, ColorDepth::TrueColor),
+            Theme::from_palette(light.rgb)
+        );
+        assert_eq!(
+            Theme::new(&BUNDLED[0], ColorDepth::Ansi16),
+            Theme::from_palette(Palette::ANSI)
+        );
+        assert_eq!(
+            Theme::new(light, ColorDepth::Ansi16),
+            Theme::from_palette(light.rgb.adapt(ColorDepth::Ansi16))
+        );
+        assert_eq!(Theme::new(light, ColorDepth::Mono), Theme::mono());
+    }
+}
@@ -1,74 +1,360 @@
-use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use qrcode::{EcLevel, QrCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap};
 use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{App, IntroPhase};
+use crate::command::CommandState;
+use crate::hyperlink::Link;
+use crate::keymap::{Action, Keymap};
+use crate::markdown;
+use crate::portfolio::{self, ContactEntry, ProjectCategory, Role, SkillGroup, Tab};
+use crate::posts::Post;
+use crate::search::SearchState;
+use crate::theme::Theme;
+
+/// Screen regions that respond to the mouse, as laid out by the last
+/// [`render`].
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    /// Tab bar labels and the index of the tab each one switches to.
+    pub tabs: Vec<(Rect, usize)>,
+    /// Rows of the list on the active tab (projects, timeline entries or
+    /// posts) and the selection index of the item on each.
+    pub items: Vec<(Rect, usize)>,
+    /// The content block, which scrolls with the mouse wheel.
+    pub content: Rect,
+    /// Links drawn in the frame, which the terminal can make clickable.
+    pub links: Vec<Link>,
+}
+
+impl HitMap {
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        hit(&self.tabs, column, row)
+    }
+
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        hit(&self.items, column, row)
+    }
+
+    pub fn in_content(&self, column: u16, row: u16) -> bool {
+        contains(self.content, column, row)
+    }
+}
+
+fn hit(regions: &[(Rect, usize)], column: u16, row: u16) -> Option<usize> {
+    regions
+        .iter()
+        .find(|(rect, _)| contains(*rect, column, row))
+        .map(|(_, idx)| *idx)
+}
 
-use crate::app::{App, IntroPhase, Tab};
-use crate::content;
-use crate::theme;
+fn contains(rect: Rect, column: u16, row: u16) -> bool {
+    (rect.x..rect.x + rect.width).contains(&column) && (rect.y..rect.y + rect.height).contains(&row)
+}
 
-/// Render the entire portfolio UI into the given frame.
-pub fn render(app: &App, f: &mut Frame) {
+/// Turn the list lines holding each item into clickable rows of `area`,
+/// given the list's scroll offset. `rows` pairs a line index with the
+/// selection index of the item on it.
+fn list_hits(area: Rect, rows: &[(usize, usize)], scroll: usize) -> Vec<(Rect, usize)> {
+    rows.iter()
+        .filter(|(line, _)| (scroll..scroll + area.height as usize).contains(line))
+        .map(|&(line, idx)| {
+            let row = Rect {
+                y: area.y + (line - scroll) as u16,
+                height: 1,
+                ..area
+            };
+            (row, idx)
+        })
+        .collect()
+}
+
+/// Render the entire portfolio UI into the given frame, returning where
+/// the clickable parts ended up.
+pub fn render(app: &App, f: &mut Frame) -> HitMap {
     let area = f.area();
+    let mut hits = HitMap::default();
 
     // Clear the screen first
-    f.render_widget(Clear, area);
+    clear(&app.theme, f, area);
 
     if !app.intro_done() {
         render_intro(app, f, area);
-        return;
+        return hits;
     }
 
     // ── Outer layout: header, tab bar, content, footer ─────────
     let chunks = Layout::vertical([
-        Constraint::Length(banner_height() + 4), // banner + blank + subtitle + border
+        Constraint::Length(banner_height(app) + 4), // banner + blank + subtitle + border
         Constraint::Length(3),                   // tab bar
         Constraint::Min(6),                      // content
         Constraint::Length(1),                   // footer
     ])
     .split(area);
 
-    render_header(f, chunks[0]);
-    render_tabs(app, f, chunks[1]);
-    render_content(app, f, chunks[2]);
+    render_header(app, f, chunks[0]);
+    render_tabs(app, f, chunks[1], &mut hits);
+    match app.searching() {
+        Some(search) => render_search(&app.theme, search, f, chunks[2]),
+        None => render_content(app, f, chunks[2], &mut hits),
+    }
+    if let Some(toast) = &app.toast {
+        render_toast(&app.theme, &toast.text, f, chunks[2]);
+    }
+    if let Some(selected) = app.picking_theme() {
+        let names: Vec<&str> = app.schemes().iter().map(|s| s.name.as_ref()).collect();
+        let active = Some(app.scheme());
+        render_picker(&app.theme, " Theme ", &names, selected, active, f, chunks[2]);
+    }
+    if let Some(scroll) = app.showing_help() {
+        // Inside the content block's borders
+        let area = chunks[2].inner(Margin::new(1, 1));
+        render_help(app, scroll, f, area);
+    }
+    if app.showing_contact_card() {
+        // The code needs more room than the content block, so it
+        // covers everything but the footer
+        let area = Rect {
+            height: area.height - chunks[3].height,
+            ..area
+        };
+        render_contact_card(app, f, area);
+    }
     render_footer(app, f, chunks[3]);
+    hits
+}
+
+/// Blank `area`, leaving the theme's background.
+fn clear(theme: &Theme, f: &mut Frame, area: Rect) {
+    f.render_widget(Clear, area);
+    f.render_widget(Block::new().style(theme.background), area);
+}
+
+// ── Toast ──────────────────────────────────────────────────────
+
+/// Render a one-line notice in the bottom-right corner of the content
+/// block.
+fn render_toast(theme: &Theme, text: &str, f: &mut Frame, area: Rect) {
+    let inside = area.inner(Margin::new(2, 1));
+    let text = truncate(text, inside.width.saturating_sub(4) as usize);
+    let width = (text.width() as u16 + 4).min(inside.width);
+    let height = 3.min(inside.height);
+    let popup = Rect {
+        x: inside.right().saturating_sub(width),
+        y: inside.bottom().saturating_sub(height),
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .padding(Padding::new(1, 1, 0, 0));
+    let inner = block.inner(popup);
+    clear(theme, f, popup);
+    f.render_widget(block, popup);
+    f.render_widget(Paragraph::new(Line::styled(text, theme.toast)), inner);
+}
+
+// ── Help overlay ───────────────────────────────────────────────
+
+/// Render the key bindings as a popup over the content.
+fn render_help(app: &App, scroll: usize, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let sections = app.help_sections();
+    let key_w = sections
+        .iter()
+        .flat_map(|s| &s.entries)
+        .map(|(keys, _)| keys.width())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        lines.push(Line::styled(section.title, theme.category_header));
+        for (keys, description) in &section.entries {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {keys:<key_w$}  "), theme.key_hint),
+                Span::styled(*description, theme.text_dim),
+            ]));
+        }
+    }
+
+    // Widest line inside a border with 1 column of padding
+    let widest = lines.iter().map(Line::width).max().unwrap_or(0);
+    let width = (widest as u16 + 4).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(Span::styled(" Keys ", theme.category_header))
+        .padding(Padding::new(1, 1, 0, 0));
+    let inner = block.inner(popup);
+    clear(theme, f, popup);
+    f.render_widget(block, popup);
+
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll.min(max_scroll) as u16, 0));
+    f.render_widget(text, inner);
+}
+
+// ── Contact card ───────────────────────────────────────────────
+
+/// Modules of light margin around the QR code. The standard asks for
+/// four; two keep the code on smaller terminals and still scan.
+const QR_QUIET_ZONE: usize = 2;
+
+/// Render the contact card as a QR code, or say how much room it needs.
+fn render_contact_card(app: &App, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    clear(theme, f, area);
+
+    let card = app.contact_card();
+    let code = match QrCode::with_error_correction_level(card.as_bytes(), EcLevel::L) {
+        Ok(code) => qr_lines(theme, &code, app.ascii),
+        Err(e) => {
+            log::error!("Contact card does not fit in a QR code: {e}");
+            Vec::new()
+        }
+    };
+    let caption = Line::from(vec![
+        Span::styled("Scan to add ", theme.text_dim),
+        Span::styled(app.portfolio.name.as_str(), theme.text_bold),
+        Span::styled(" to your contacts", theme.text_dim),
+    ]);
+
+    let code_w = code.first().map_or(0, Line::width) as u16;
+    let code_h = code.len() as u16;
+    let lines = if !code.is_empty() && code_w <= area.width && code_h + 2 <= area.height {
+        let mut lines = code;
+        lines.extend([Line::default(), caption]);
+        lines
+    } else {
+        let save = "ssh <host> vcard > contact.vcf";
+        let mut lines = vec![caption, Line::default()];
+        if code.is_empty() {
+            lines.push(Line::styled(
+                format!("Save the card with: {save}"),
+                theme.text_muted,
+            ));
+        } else {
+            lines.push(Line::styled(
+                format!(
+                    "Enlarge the terminal to {code_w}\u{00d7}{} for the QR code,",
+                    code_h + 3
+                ),
+                theme.text_muted,
+            ));
+            lines.push(Line::styled(
+                format!("or save the card with: {save}"),
+                theme.text_muted,
+            ));
+        }
+        lines
+    };
+
+    let height = (lines.len() as u16).min(area.height);
+    let text = Paragraph::new(Text::from(lines)).alignment(Alignment::Center);
+    let popup = Rect {
+        y: area.y + (area.height - height) / 2,
+        height,
+        ..area
+    };
+    f.render_widget(text, popup);
+}
+
+/// A QR code drawn with half blocks, two rows of modules per line, or
+/// in ASCII with `#` for the light modules.
+fn qr_lines(theme: &Theme, code: &QrCode, ascii: bool) -> Vec<Line<'static>> {
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QR_QUIET_ZONE;
+    // Coordinates in the quiet zone wrap around to out of range
+    let light = |x: usize, y: usize| {
+        let (x, y) = (x.wrapping_sub(QR_QUIET_ZONE), y.wrapping_sub(QR_QUIET_ZONE));
+        x >= width || y >= width || colors[y * width + x] == qrcode::Color::Light
+    };
+
+    // Without block elements each module takes a line of its own, and two
+    // columns to stay square, so the code is twice as big each way
+    if ascii {
+        return (0..size)
+            .map(|y| {
+                let row: String = (0..size)
+                    .map(|x| if light(x, y) { "##" } else { "  " })
+                    .collect();
+                Line::styled(row, theme.qr_code)
+            })
+            .collect();
+    }
+
+    (0..size)
+        .step_by(2)
+        .map(|y| {
+            let row: String = (0..size)
+                .map(|x| match (light(x, y), y + 1 < size && light(x, y + 1)) {
+                    (true, true) => '\u{2588}',
+                    (true, false) => '\u{2580}',
+                    (false, true) => '\u{2584}',
+                    (false, false) => ' ',
+                })
+                .collect();
+            Line::styled(row, theme.qr_code)
+        })
+        .collect()
 }
 
 // ── Intro animation ────────────────────────────────────────────
 
 fn render_intro(app: &App, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let chars_shown = match app.intro {
         IntroPhase::Typing { chars_shown } => chars_shown,
-        IntroPhase::Pause { .. } | IntroPhase::Done => content::banner_char_count(),
+        IntroPhase::Pause { .. } | IntroPhase::Done => app.portfolio.banner_char_count(),
     };
 
-    let max_w = banner_width();
+    let max_w = banner_width(app);
 
     // Build the revealed portion of the banner.
     // Pad each line to the same width so centering keeps alignment.
     let mut lines: Vec<Line> = Vec::new();
     let mut remaining = chars_shown;
 
-    for &banner_line in content::BANNER {
+    for banner_line in &app.portfolio.banner {
         if remaining == 0 {
             break;
         }
-        let show = remaining.min(banner_line.len());
-        let revealed = &banner_line[..show];
+        let line_len = banner_line.chars().count();
+        let show = remaining.min(line_len);
+        let revealed: String = banner_line.chars().take(show).collect();
 
         // Pad the revealed portion to max banner width
         let padded = format!("{:<width$}", revealed, width = max_w);
-        let mut spans = vec![Span::styled(padded, theme::HEADER)];
+        let mut spans = vec![Span::styled(padded, theme.header)];
 
         // Show a blinking cursor at the end of the current typing line
-        if show < banner_line.len() {
-            spans.push(Span::styled("\u{2588}", theme::INTRO_CURSOR));
+        if show < line_len {
+            spans.push(Span::styled("\u{2588}", theme.intro_cursor));
         }
 
         lines.push(Line::from(spans));
 
         // consume chars + 1 for the implicit newline
-        remaining = remaining.saturating_sub(banner_line.len() + 1);
+        remaining = remaining.saturating_sub(line_len + 1);
     }
 
     // Center the banner vertically
@@ -88,25 +374,33 @@ fn render_intro(app: &App, f: &mut Frame, area: Rect) {
 
 // ── Header (ASCII banner) ──────────────────────────────────────
 
-fn banner_height() -> u16 {
-    content::BANNER.len() as u16
+fn banner_height(app: &App) -> u16 {
+    app.portfolio.banner.len() as u16
 }
 
-fn banner_width() -> usize {
-    content::BANNER.iter().map(|l| l.len()).max().unwrap_or(0)
+fn banner_width(app: &App) -> usize {
+    app.portfolio
+        .banner
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0)
 }
 
-fn render_header(f: &mut Frame, area: Rect) {
-    let max_w = banner_width();
+fn render_header(app: &App, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let max_w = banner_width(app);
 
     // Pad each banner line to the same width so Alignment::Center
     // shifts them as a uniform block instead of centering each
     // line independently (which breaks the ASCII art).
-    let lines: Vec<Line> = content::BANNER
+    let lines: Vec<Line> = app
+        .portfolio
+        .banner
         .iter()
-        .map(|&l| {
+        .map(|l| {
             let padded = format!("{:<width$}", l, width = max_w);
-            Line::from(Span::styled(padded, theme::HEADER))
+            Line::from(Span::styled(padded, theme.header))
         })
         .collect();
 
@@ -118,9 +412,9 @@ fn render_header(f: &mut Frame, area: Rect) {
 
     let subtitle = Line::from(vec![
         Span::raw(" ".repeat(sub_pad_left)),
-        Span::styled("software engineer", theme::TEXT_DIM),
-        Span::styled("  \u{00b7}  ", theme::TEXT_MUTED),
-        Span::styled("France", theme::TEXT_DIM),
+        Span::styled("software engineer", theme.text_dim),
+        Span::styled("  \u{00b7}  ", theme.text_muted),
+        Span::styled("France", theme.text_dim),
         Span::raw(" ".repeat(sub_pad_right)),
     ]);
 
@@ -133,133 +427,317 @@ fn render_header(f: &mut Frame, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::BOTTOM)
-                .border_style(theme::BORDER),
+                .border_style(theme.border),
         );
     f.render_widget(text, area);
 }
 
 // ── Tab bar ────────────────────────────────────────────────────
 
-fn render_tabs(app: &App, f: &mut Frame, area: Rect) {
-    let tabs: Vec<Span> = Tab::ALL
+fn render_tabs(app: &App, f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let theme = &app.theme;
+    const SPACER: &str = "   ";
+
+    let labels: Vec<Vec<Span>> = app
+        .portfolio
+        .tabs
         .iter()
         .enumerate()
-        .flat_map(|(i, t)| {
-            let num = Span::styled(format!("{}", i + 1), theme::TAB_NUMBER);
-            let sep = Span::styled(":", theme::TAB_NUMBER);
-            let style = if *t == app.tab {
-                theme::TAB_ACTIVE
+        .map(|(i, t)| {
+            let style = if i == app.tab {
+                theme.tab_active
             } else {
-                theme::TAB_INACTIVE
+                theme.tab_inactive
             };
-            let label = Span::styled(format!("{}", t.label()), style);
-            let spacer = Span::raw("   ");
-            vec![num, sep, label, spacer]
+            let label = Span::styled(t.title(), style);
+            match app.keymap.hint(Action::GoToTab(i)) {
+                Some(key) => vec![
+                    Span::styled(key, theme.tab_number),
+                    Span::styled(":", theme.tab_number),
+                    label,
+                ],
+                None => vec![label],
+            }
         })
         .collect();
 
-    let line = Line::from(tabs);
+    let widths: Vec<usize> = labels
+        .iter()
+        .map(|l| l.iter().map(Span::width).sum())
+        .collect();
+    let (first, last) = visible_tabs(&widths, SPACER.len(), app.tab, area.width as usize);
+
+    // Each piece of the bar, with the tab a click on it switches to
+    let mut pieces: Vec<(Vec<Span>, Option<usize>)> = Vec::new();
+    if first > 0 {
+        pieces.push((vec![Span::styled("\u{2039} ", theme.tab_number)], Some(first - 1)));
+    }
+    for (i, label) in labels.into_iter().enumerate().take(last + 1).skip(first) {
+        if i > first {
+            pieces.push((vec![Span::raw(SPACER)], None));
+        }
+        pieces.push((label, Some(i)));
+    }
+    if last + 1 < widths.len() {
+        pieces.push((vec![Span::styled(" \u{203a}", theme.tab_number)], Some(last + 1)));
+    }
+
+    // Mirror the centering done by the paragraph below
+    let line_width: usize = pieces.iter().flat_map(|(p, _)| p).map(Span::width).sum();
+    let mut x = area.x + (area.width.saturating_sub(line_width as u16)) / 2;
+    for (piece, tab) in &pieces {
+        let width = piece.iter().map(Span::width).sum::<usize>() as u16;
+        if let Some(tab) = tab {
+            hits.tabs.push((Rect { x, width, ..area }, *tab));
+        }
+        x += width;
+    }
+
+    let line = Line::from(pieces.into_iter().flat_map(|(p, _)| p).collect::<Vec<_>>());
     let paragraph = Paragraph::new(line).alignment(Alignment::Center);
     f.render_widget(paragraph, area);
 }
 
+/// Pick the range of tabs (inclusive) to show in the tab bar. All tabs
+/// if they fit, otherwise as many as fit around the active tab, leaving
+/// room for the overflow indicators on both sides.
+fn visible_tabs(widths: &[usize], spacer: usize, active: usize, available: usize) -> (usize, usize) {
+    if widths.is_empty() {
+        return (0, 0);
+    }
+    let total = widths.iter().sum::<usize>() + spacer * (widths.len() - 1);
+    if total <= available {
+        return (0, widths.len() - 1);
+    }
+
+    let available = available.saturating_sub(4); // "‹ " and " ›"
+    let active = active.min(widths.len() - 1);
+    let (mut first, mut last) = (active, active);
+    let mut used = widths[active];
+    loop {
+        let mut grew = false;
+        if last + 1 < widths.len() && used + spacer + widths[last + 1] <= available {
+            last += 1;
+            used += spacer + widths[last];
+            grew = true;
+        }
+        if first > 0 && used + spacer + widths[first - 1] <= available {
+            first -= 1;
+            used += spacer + widths[first];
+            grew = true;
+        }
+        if !grew {
+            return (first, last);
+        }
+    }
+}
+
 // ── Content area ───────────────────────────────────────────────
 
-fn render_content(app: &App, f: &mut Frame, area: Rect) {
+fn render_content(app: &App, f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let theme = &app.theme;
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(theme::BORDER)
+        .border_style(theme.border)
         .padding(Padding::new(2, 2, 1, 1));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
+    hits.content = area;
 
-    match app.tab {
-        Tab::About => render_about(app, f, inner),
-        Tab::Projects => render_projects(app, f, inner),
-        Tab::Skills => render_skills(app, f, inner),
-        Tab::Contact => render_contact(app, f, inner),
+    match app.current_tab() {
+        Some(Tab::Text { body, .. }) => render_text(app, body, f, inner),
+        Some(Tab::Projects { categories, .. }) => {
+            render_projects(app, categories, f, inner, hits)
+        }
+        Some(Tab::Timeline { entries, .. }) => render_timeline(app, entries, f, inner, hits),
+        Some(Tab::Posts { posts, .. }) => match app.reading() {
+            Some(post) => render_post_reader(app, post, f, inner),
+            None => render_posts(app, posts, f, inner, hits),
+        },
+        Some(Tab::Skills { groups, .. }) => render_skills(app, groups, f, inner),
+        Some(Tab::KeyValue {
+            intro,
+            entries,
+            outro,
+            ..
+        }) => render_key_value(app, intro, entries, outro, f, inner, hits),
+        None => {}
     }
 }
 
-// ── About tab ──────────────────────────────────────────────────
+// ── Text tab (Markdown page) ───────────────────────────────────
 
-fn render_about(app: &App, f: &mut Frame, area: Rect) {
-    let mut lines: Vec<Line> = Vec::new();
-
-    for (i, &line_str) in content::ABOUT_LINES.iter().enumerate() {
-        if i == 0 {
-            // First line is the greeting — make it bold
-            lines.push(Line::from(Span::styled(line_str, theme::TEXT_BOLD)));
-        } else if line_str.is_empty() {
-            lines.push(Line::from(""));
-        } else {
-            lines.push(Line::from(Span::styled(line_str, theme::TEXT)));
-        }
-    }
+fn render_text(app: &App, body: &str, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let lines = markdown::render(body, area.width as usize, theme);
 
     let total_lines = lines.len();
     let viewport_h = area.height as usize;
 
-    let text = Paragraph::new(Text::from(lines))
-        .scroll((app.scroll_offset as u16, 0))
-        .wrap(Wrap { trim: false });
+    let text = Paragraph::new(Text::from(lines)).scroll((app.scroll_offset as u16, 0));
     f.render_widget(text, area);
 
     if total_lines > viewport_h {
-        render_scroll_indicator(f, area, app.scroll_offset, total_lines, viewport_h);
+        render_scroll_indicator(theme, f, area, app.scroll_offset, total_lines, viewport_h);
     }
 }
 
 // ── Projects tab (telescope-style split pane) ──────────────────
 
-fn render_projects(app: &App, f: &mut Frame, area: Rect) {
+fn render_projects(
+    app: &App,
+    categories: &[ProjectCategory],
+    f: &mut Frame,
+    area: Rect,
+    hits: &mut HitMap,
+) {
     // Split into left (40%) and right (60%) panes
     let panes = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(area);
 
-    render_project_list(app, f, panes[0]);
-    render_project_detail(app, f, panes[1]);
+    render_project_list(app, categories, f, panes[0], hits);
+    render_project_detail(app, categories, f, panes[1], hits);
+
+    if let Some(selected) = app.picking_tag() {
+        render_tag_picker(app, selected, f, area);
+    }
+}
+
+/// Render the tag picker as a popup over the project browser.
+fn render_tag_picker(app: &App, selected: usize, f: &mut Frame, area: Rect) {
+    let tags = app.tag_options();
+    let options: Vec<&str> = std::iter::once("All projects")
+        .chain(tags.iter().map(String::as_str))
+        .collect();
+    let active = match app.filter() {
+        Some(tag) => tags
+            .iter()
+            .position(|t| t.eq_ignore_ascii_case(tag))
+            .map(|i| i + 1),
+        None => Some(0),
+    };
+    render_picker(&app.theme, " Filter by tech ", &options, selected, active, f, area);
+}
+
+/// Render a list of options as a popup centred in `area`, with the
+/// `selected` one highlighted and the `active` one, the one in effect,
+/// marked.
+fn render_picker(
+    theme: &Theme,
+    title: &str,
+    options: &[&str],
+    selected: usize,
+    active: Option<usize>,
+    f: &mut Frame,
+    area: Rect,
+) {
+    // Arrow + widest option, inside a border with 1 column of padding
+    let widest = options.iter().map(|o| o.width()).max().unwrap_or(0);
+    let width = (widest as u16 + 3 + 4).max(24).min(area.width);
+    let height = (options.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(Span::styled(title, theme.category_header))
+        .padding(Padding::new(1, 1, 0, 0));
+    let inner = block.inner(popup);
+    clear(theme, f, popup);
+    f.render_widget(block, popup);
+
+    let lines: Vec<Line> = options
+        .iter()
+        .enumerate()
+        .map(|(i, option)| {
+            let style = if i == selected {
+                theme.project_selected
+            } else if active == Some(i) {
+                theme.filter_tag
+            } else {
+                theme.project_list_item
+            };
+            let arrow = if i == selected { " \u{25b8} " } else { "   " };
+            Line::from(vec![
+                Span::styled(arrow, theme.project_arrow),
+                Span::styled(*option, style),
+            ])
+        })
+        .collect();
+
+    let scroll = compute_auto_scroll(selected, inner.height as usize, lines.len());
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll as u16, 0));
+    f.render_widget(text, inner);
 }
 
 /// Render the left pane: project list grouped by category.
-fn render_project_list(app: &App, f: &mut Frame, area: Rect) {
+fn render_project_list(
+    app: &App,
+    categories: &[ProjectCategory],
+    f: &mut Frame,
+    area: Rect,
+    hits: &mut HitMap,
+) {
+    let theme = &app.theme;
     let block = Block::default()
         .borders(Borders::RIGHT)
-        .border_style(theme::BORDER)
+        .border_style(theme.border)
         .padding(Padding::new(1, 1, 0, 0));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     let mut lines: Vec<Line> = Vec::new();
+    let mut rows: Vec<(usize, usize)> = Vec::new();
     let mut flat_idx: usize = 0;
+    let filter = app.filter();
 
-    for (cat_idx, cat) in content::PROJECT_CATEGORIES.iter().enumerate() {
+    if let Some(tag) = filter {
+        let shown = portfolio::total_project_count(categories, filter);
+        let total = portfolio::total_project_count(categories, None);
+        lines.push(Line::from(vec![
+            Span::styled("Filter ", theme.project_detail_label),
+            Span::styled(tag, theme.filter_tag),
+            Span::styled(format!("  {shown}/{total}"), theme.text_muted),
+        ]));
+        lines.push(Line::from(""));
+    }
+
+    let visible = categories
+        .iter()
+        .filter(|cat| cat.projects.iter().any(|p| p.matches(filter)));
+    for (cat_idx, cat) in visible.enumerate() {
         if cat_idx > 0 {
             lines.push(Line::from(""));
         }
 
         // Category header (non-selectable)
-        lines.push(Line::from(Span::styled(cat.name, theme::CATEGORY_HEADER)));
+        lines.push(Line::from(Span::styled(cat.name.as_str(), theme.category_header)));
 
-        for project in cat.projects.iter() {
+        for project in cat.projects.iter().filter(|p| p.matches(filter)) {
             let is_selected = flat_idx == app.selected_project;
 
             let line = if is_selected {
                 Line::from(vec![
-                    Span::styled(" \u{25b8} ", theme::PROJECT_ARROW),
-                    Span::styled(project.name, theme::PROJECT_SELECTED),
+                    Span::styled(" \u{25b8} ", theme.project_arrow),
+                    Span::styled(project.name.as_str(), theme.project_selected),
                 ])
             } else {
                 Line::from(vec![
                     Span::raw("   "),
-                    Span::styled(project.name, theme::PROJECT_LIST_ITEM),
+                    Span::styled(project.name.as_str(), theme.project_list_item),
                 ])
             };
 
+            rows.push((lines.len(), flat_idx));
             lines.push(line);
             flat_idx += 1;
         }
@@ -270,25 +748,34 @@ fn render_project_list(app: &App, f: &mut Frame, area: Rect) {
 
     // Auto-scroll to keep the selected project visible.
     // Find which line the selected project is on.
-    let selected_line = find_selected_line_in_list(app.selected_project);
+    let header = if filter.is_some() { 2 } else { 0 };
+    let selected_line = header + find_selected_line_in_list(categories, app.selected_project, filter);
     let scroll_offset = compute_auto_scroll(selected_line, viewport_h, total_lines);
+    hits.items = list_hits(inner, &rows, scroll_offset);
 
     let text = Paragraph::new(Text::from(lines)).scroll((scroll_offset as u16, 0));
     f.render_widget(text, inner);
 }
 
 /// Given a flat project index, compute which line it falls on in the list pane.
-fn find_selected_line_in_list(selected: usize) -> usize {
+fn find_selected_line_in_list(
+    categories: &[ProjectCategory],
+    selected: usize,
+    filter: Option<&str>,
+) -> usize {
     let mut line: usize = 0;
     let mut flat_idx: usize = 0;
 
-    for (cat_idx, cat) in content::PROJECT_CATEGORIES.iter().enumerate() {
+    let visible = categories
+        .iter()
+        .filter(|cat| cat.projects.iter().any(|p| p.matches(filter)));
+    for (cat_idx, cat) in visible.enumerate() {
         if cat_idx > 0 {
             line += 1; // blank separator
         }
         line += 1; // category header
 
-        for _ in cat.projects.iter() {
+        for _ in cat.projects.iter().filter(|p| p.matches(filter)) {
             if flat_idx == selected {
                 return line;
             }
@@ -312,13 +799,22 @@ fn compute_auto_scroll(target_line: usize, viewport_h: usize, total_lines: usize
 }
 
 /// Render the right pane: detail view for the selected project.
-fn render_project_detail(app: &App, f: &mut Frame, area: Rect) {
+fn render_project_detail(
+    app: &App,
+    categories: &[ProjectCategory],
+    f: &mut Frame,
+    area: Rect,
+    hits: &mut HitMap,
+) {
+    let theme = &app.theme;
     let block = Block::default().padding(Padding::new(2, 1, 1, 0));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let Some((category, project)) = content::get_project_by_flat_index(app.selected_project) else {
+    let Some((category, project)) =
+        portfolio::get_project_by_flat_index(categories, app.selected_project, app.filter())
+    else {
         return;
     };
 
@@ -326,55 +822,289 @@ fn render_project_detail(app: &App, f: &mut Frame, area: Rect) {
 
     // Project name
     lines.push(Line::from(Span::styled(
-        project.name,
-        theme::PROJECT_DETAIL_NAME,
+        project.name.as_str(),
+        theme.project_detail_name,
     )));
 
     // Category
     lines.push(Line::from(Span::styled(
-        category.name,
-        theme::PROJECT_DETAIL_CATEGORY,
+        category.name.as_str(),
+        theme.project_detail_category,
     )));
 
     lines.push(Line::from(""));
 
     // Description
-    lines.push(Line::from(Span::styled(project.description, theme::TEXT)));
+    lines.extend(markdown::render(&project.description, inner.width as usize, theme));
 
     lines.push(Line::from(""));
 
     // Tech stack
     lines.push(Line::from(vec![
-        Span::styled("Tech  ", theme::PROJECT_DETAIL_LABEL),
-        Span::styled(project.tech, theme::TEXT_DIM),
+        Span::styled("Tech  ", theme.project_detail_label),
+        Span::styled(project.tech.as_str(), theme.text_dim),
     ]));
 
     lines.push(Line::from(""));
 
     // URL
     lines.push(Line::from(vec![
-        Span::styled("URL   ", theme::PROJECT_DETAIL_LABEL),
-        Span::styled(project.url, theme::LINK),
+        Span::styled("URL   ", theme.project_detail_label),
+        Span::styled(project.url.as_str(), theme.link),
     ]));
+    hits.links.extend(Link::detect(&project.url));
+
+    let text = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+    f.render_widget(text, inner);
+}
+
+// ── Timeline tab (work history split pane) ─────────────────────
+
+fn render_timeline(app: &App, roles: &[Role], f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    render_timeline_list(app, roles, f, panes[0], hits);
+    render_role_detail(app, roles, f, panes[1]);
+}
+
+/// Render the left pane: roles as a vertical timeline, joined by connectors.
+fn render_timeline_list(app: &App, roles: &[Role], f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .borders(Borders::RIGHT)
+        .border_style(theme.border)
+        .padding(Padding::new(1, 1, 0, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    for (i, role) in roles.iter().enumerate() {
+        let is_selected = i == app.selected_role;
+        let is_last = i + 1 == roles.len();
+        let connector = if is_last { "  " } else { "\u{2502} " };
+
+        let (dot, dot_style, title_style) = if is_selected {
+            ("\u{25cf} ", theme.timeline_dot_selected, theme.project_selected)
+        } else {
+            ("\u{25cb} ", theme.timeline_dot, theme.project_list_item)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(dot, dot_style),
+            Span::styled(role.title.as_str(), title_style),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(connector, theme.timeline_connector),
+            Span::styled(
+                format!("{} \u{00b7} {}", role.client, role.dates()),
+                theme.timeline_meta,
+            ),
+        ]));
+        if !is_last {
+            lines.push(Line::from(Span::styled(connector, theme.timeline_connector)));
+        }
+    }
+
+    let total_lines = lines.len();
+    let viewport_h = inner.height as usize;
+
+    // Keep the selected entry visible; each entry starts every 3 lines
+    let selected_line = app.selected_role * 3;
+    let scroll_offset = compute_auto_scroll(selected_line, viewport_h, total_lines);
+    // The title and meta lines of an entry are clickable
+    let rows: Vec<(usize, usize)> = (0..roles.len())
+        .flat_map(|i| [(i * 3, i), (i * 3 + 1, i)])
+        .collect();
+    hits.items = list_hits(inner, &rows, scroll_offset);
+
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll_offset as u16, 0));
+    f.render_widget(text, inner);
+}
+
+/// Render the right pane: detail view for the selected role.
+fn render_role_detail(app: &App, roles: &[Role], f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let block = Block::default().padding(Padding::new(2, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(role) = roles.get(app.selected_role) else {
+        return;
+    };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(role.title.as_str(), theme.project_detail_name)),
+        Line::from(Span::styled(
+            role.client.as_str(),
+            theme.project_detail_category,
+        )),
+        Line::from(Span::styled(role.dates(), theme.text_dim)),
+    ];
+
+    if !role.summary.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(markdown::render(&role.summary, inner.width as usize, theme));
+    }
+
+    if !role.highlights.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Highlights",
+            theme.project_detail_label,
+        )));
+        // Render as a Markdown list so long highlights wrap under their bullet
+        let list: String = role
+            .highlights
+            .iter()
+            .map(|h| format!("- {h}\n"))
+            .collect();
+        lines.extend(markdown::render(&list, inner.width as usize, theme));
+    }
 
     let text = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
     f.render_widget(text, inner);
 }
 
+// ── Posts tab (post list + reader) ─────────────────────────────
+
+fn render_posts(app: &App, posts: &[Post], f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let theme = &app.theme;
+    if posts.is_empty() {
+        let text = Paragraph::new(Span::styled("No posts yet.", theme.text_muted));
+        f.render_widget(text, area);
+        return;
+    }
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    render_post_list(app, posts, f, panes[0], hits);
+    render_post_preview(app, posts, f, panes[1]);
+}
+
+/// Render the left pane: post titles with their dates, newest first.
+fn render_post_list(app: &App, posts: &[Post], f: &mut Frame, area: Rect, hits: &mut HitMap) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .borders(Borders::RIGHT)
+        .border_style(theme.border)
+        .padding(Padding::new(1, 1, 0, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    for (i, post) in posts.iter().enumerate() {
+        let title = if i == app.selected_post {
+            Line::from(vec![
+                Span::styled(" \u{25b8} ", theme.project_arrow),
+                Span::styled(post.title.as_str(), theme.project_selected),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("   "),
+                Span::styled(post.title.as_str(), theme.project_list_item),
+            ])
+        };
+        lines.push(title);
+        lines.push(Line::from(vec![
+            Span::raw("   "),
+            Span::styled(post.date.as_str(), theme.text_dim),
+        ]));
+    }
+
+    let total_lines = lines.len();
+    let viewport_h = inner.height as usize;
+
+    // Each post takes two lines
+    let selected_line = app.selected_post * 2;
+    let scroll_offset = compute_auto_scroll(selected_line, viewport_h, total_lines);
+    let rows: Vec<(usize, usize)> = (0..posts.len())
+        .flat_map(|i| [(i * 2, i), (i * 2 + 1, i)])
+        .collect();
+    hits.items = list_hits(inner, &rows, scroll_offset);
+
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll_offset as u16, 0));
+    f.render_widget(text, inner);
+}
+
+/// Render the right pane: metadata and the opening of the selected post.
+fn render_post_preview(app: &App, posts: &[Post], f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let block = Block::default().padding(Padding::new(2, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(post) = posts.get(app.selected_post) else {
+        return;
+    };
+
+    let mut lines = post_header(theme, post);
+    lines.extend(markdown::render(&post.body, inner.width as usize, theme));
+
+    // The preview is cut off at the bottom of the pane; Enter opens the rest
+    let text = Paragraph::new(Text::from(lines));
+    f.render_widget(text, inner);
+}
+
+/// Full-width, scrollable view of a single post.
+fn render_post_reader(app: &App, post: &Post, f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    let mut lines = post_header(theme, post);
+    lines.extend(markdown::render(&post.body, area.width as usize, theme));
+
+    let total_lines = lines.len();
+    let viewport_h = area.height as usize;
+
+    let text = Paragraph::new(Text::from(lines)).scroll((app.scroll_offset as u16, 0));
+    f.render_widget(text, area);
+
+    if total_lines > viewport_h {
+        render_scroll_indicator(theme, f, area, app.scroll_offset, total_lines, viewport_h);
+    }
+}
+
+/// Title, then date and tags, then a blank line
+/// ([`app::POST_HEADER_LINES`] lines in all).
+fn post_header<'a>(theme: &Theme, post: &'a Post) -> Vec<Line<'a>> {
+    let mut meta = vec![Span::styled(post.date.as_str(), theme.text_dim)];
+    if !post.tags.is_empty() {
+        meta.push(Span::styled("  \u{00b7}  ", theme.text_muted));
+        meta.push(Span::styled(post.tags.join(", "), theme.project_detail_category));
+    }
+
+    vec![
+        Line::from(Span::styled(post.title.as_str(), theme.project_detail_name)),
+        Line::from(meta),
+        Line::from(""),
+    ]
+}
+
 // ── Skills tab ─────────────────────────────────────────────────
 
-fn render_skills(app: &App, f: &mut Frame, area: Rect) {
+fn render_skills(app: &App, groups: &[SkillGroup], f: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let mut lines: Vec<Line> = Vec::new();
 
-    for (i, group) in content::SKILLS.iter().enumerate() {
+    for (i, group) in groups.iter().enumerate() {
         if i > 0 {
             lines.push(Line::from(""));
         }
 
-        lines.push(Line::from(Span::styled(group.name, theme::SKILL_GROUP)));
+        lines.push(Line::from(Span::styled(group.name.as_str(), theme.skill_group)));
 
         let items_str = group.items.join("  \u{00b7}  ");
-        lines.push(Line::from(Span::styled(items_str, theme::SKILL_ITEM)));
+        lines.push(Line::from(Span::styled(items_str, theme.skill_item)));
     }
 
     let total_lines = lines.len();
@@ -386,74 +1116,370 @@ fn render_skills(app: &App, f: &mut Frame, area: Rect) {
     f.render_widget(text, area);
 
     if total_lines > viewport_h {
-        render_scroll_indicator(f, area, app.scroll_offset, total_lines, viewport_h);
+        render_scroll_indicator(theme, f, area, app.scroll_offset, total_lines, viewport_h);
     }
 }
 
-// ── Contact tab ────────────────────────────────────────────────
+// ── Key/value tab (e.g. Contact) ───────────────────────────────
 
-fn render_contact(app: &App, f: &mut Frame, area: Rect) {
-    let mut lines: Vec<Line> = Vec::new();
-
-    lines.push(Line::from(Span::styled(
-        "Want to get in touch? Here's where you can find me:",
-        theme::TEXT,
-    )));
+fn render_key_value(
+    app: &App,
+    intro: &str,
+    entries: &[ContactEntry],
+    outro: &str,
+    f: &mut Frame,
+    area: Rect,
+    hits: &mut HitMap,
+) {
+    let theme = &app.theme;
+    let width = area.width as usize;
+    // Text is wrapped up front and entries are cut to one row each, so
+    // every entry lands on a known line
+    let mut lines: Vec<Line> = wrap_words(intro, width)
+        .into_iter()
+        .map(|l| Line::from(Span::styled(l, theme.text)))
+        .collect();
     lines.push(Line::from(""));
 
-    for entry in content::CONTACT_ENTRIES {
+    let first_entry = lines.len();
+    for (i, entry) in entries.iter().enumerate() {
+        let (arrow, label_style) = if i == app.selected_entry {
+            ("\u{25b8} ", theme.project_selected)
+        } else {
+            ("  ", theme.contact_label)
+        };
+        let label = format!("{:<10}", entry.label);
+        let value_width = width.saturating_sub(arrow.width() + label.width());
         lines.push(Line::from(vec![
-            Span::styled(format!("  {:<10}", entry.label), theme::CONTACT_LABEL),
-            Span::styled(entry.value, theme::LINK),
+            Span::styled(arrow, theme.project_arrow),
+            Span::styled(label, label_style),
+            Span::styled(truncate(&entry.value, value_width), theme.link),
         ]));
+        hits.links.extend(Link::detect(&entry.value));
     }
 
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        content::CONTACT_OUTRO,
-        theme::TEXT_DIM,
-    )));
+    lines.extend(
+        wrap_words(outro, width)
+            .into_iter()
+            .map(|l| Line::from(Span::styled(l, theme.text_dim))),
+    );
 
     let total_lines = lines.len();
     let viewport_h = area.height as usize;
 
-    let text = Paragraph::new(Text::from(lines))
-        .scroll((app.scroll_offset as u16, 0))
-        .wrap(Wrap { trim: false });
+    let selected_line = first_entry + app.selected_entry;
+    let scroll_offset = compute_auto_scroll(selected_line, viewport_h, total_lines);
+    let rows: Vec<(usize, usize)> = (0..entries.len()).map(|i| (first_entry + i, i)).collect();
+    hits.items = list_hits(area, &rows, scroll_offset);
+
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll_offset as u16, 0));
     f.render_widget(text, area);
 
     if total_lines > viewport_h {
-        render_scroll_indicator(f, area, app.scroll_offset, total_lines, viewport_h);
+        render_scroll_indicator(theme, f, area, scroll_offset, total_lines, viewport_h);
+    }
+}
+
+// ── Search ─────────────────────────────────────────────────────
+
+/// Render the search prompt and its ranked results in place of the
+/// content block.
+fn render_search(theme: &Theme, search: &SearchState, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::vertical([
+        Constraint::Length(1), // prompt
+        Constraint::Length(1), // blank
+        Constraint::Min(1),    // results
+    ])
+    .split(inner);
+
+    let count = if search.query.is_empty() {
+        String::new()
+    } else {
+        format!("{} results", search.results.len())
+    };
+    let prompt = Line::from(vec![
+        Span::styled("/ ", theme.search_prompt),
+        Span::styled(search.query.as_str(), theme.text),
+        Span::styled("\u{2588}", theme.text_muted),
+    ]);
+    f.render_widget(Paragraph::new(prompt), rows[0]);
+    f.render_widget(
+        Paragraph::new(Span::styled(count, theme.text_muted)).alignment(Alignment::Right),
+        rows[0],
+    );
+
+    let area = rows[2];
+    if search.results.is_empty() {
+        let hint = if search.query.is_empty() {
+            "Type to search projects, skills and pages"
+        } else {
+            "No matches"
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(hint, theme.text_muted)),
+            area,
+        );
+        return;
     }
+
+    let width = area.width as usize;
+    let lines: Vec<Line> = search
+        .results
+        .iter()
+        .enumerate()
+        .map(|(i, hit)| {
+            let is_selected = i == search.selected;
+            let (arrow, style) = if is_selected {
+                (" \u{25b8} ", theme.project_selected)
+            } else {
+                ("   ", theme.project_list_item)
+            };
+
+            // Text on the left, where it was found on the right
+            let context = truncate(&hit.context, width / 3);
+            let text_w = width.saturating_sub(3 + 2 + context.width());
+            let mut spans = vec![Span::styled(arrow, theme.project_arrow)];
+            spans.extend(highlight(theme, &hit.text, &hit.matched, style, text_w));
+            let used: usize = spans.iter().map(|s| s.content.width()).sum();
+            let pad = width.saturating_sub(used + context.width());
+            spans.push(Span::raw(" ".repeat(pad)));
+            spans.push(Span::styled(context, theme.search_context));
+            Line::from(spans)
+        })
+        .collect();
+
+    let scroll = compute_auto_scroll(search.selected, area.height as usize, lines.len());
+    let text = Paragraph::new(Text::from(lines)).scroll((scroll as u16, 0));
+    f.render_widget(text, area);
+}
+
+/// Spans for `text` with the chars at `matched` highlighted, fitted into
+/// `max_width` columns. Long text is cut around the first match.
+fn highlight(
+    theme: &Theme,
+    text: &str,
+    matched: &[usize],
+    style: Style,
+    max_width: usize,
+) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let fits = text.width() <= max_width;
+
+    // Start a little before the first match so it has some context
+    let start = match matched.first() {
+        Some(&first) if !fits => first.saturating_sub(8),
+        _ => 0,
+    };
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut used = 0;
+    if start > 0 {
+        spans.push(Span::styled("\u{2026}", style));
+        used += 1;
+    }
+
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, &c) in chars.iter().enumerate().skip(start) {
+        let w = c.to_string().width();
+        // Keep a column for the ellipsis if more text follows
+        let reserve = if i + 1 < chars.len() { 1 } else { 0 };
+        if used + w + reserve > max_width && !fits {
+            if !run.is_empty() {
+                let current = run_style(theme, style, run_matched);
+                spans.push(Span::styled(std::mem::take(&mut run), current));
+            }
+            spans.push(Span::styled("\u{2026}", style));
+            return spans;
+        }
+        let is_match = matched.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let current = run_style(theme, style, run_matched);
+            spans.push(Span::styled(std::mem::take(&mut run), current));
+        }
+        run_matched = is_match;
+        run.push(c);
+        used += w;
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, run_style(theme, style, run_matched)));
+    }
+    spans
+}
+
+fn run_style(theme: &Theme, style: Style, matched: bool) -> Style {
+    if matched {
+        style.patch(theme.search_match)
+    } else {
+        style
+    }
+}
+
+/// Break `text` into lines of at most `width` columns at spaces. Words
+/// wider than a line get a line of their own.
+pub fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Cut `text` to `max_width` columns, ending with an ellipsis if cut.
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if out.width() + c.to_string().width() + 1 > max_width {
+            break;
+        }
+        out.push(c);
+    }
+    out.push('\u{2026}');
+    out
 }
 
 // ── Footer ─────────────────────────────────────────────────────
 
 fn render_footer(app: &App, f: &mut Frame, area: Rect) {
-    let jk_action = if app.tab == Tab::Projects {
-        "select"
+    let theme = &app.theme;
+    if let Some(prompt) = app.command_prompt() {
+        render_command_prompt(theme, prompt, f, area);
+        return;
+    }
+    if let Some(status) = &app.status {
+        let style = if status.error {
+            theme.command_error
+        } else {
+            theme.command_output
+        };
+        let text = truncate(&status.text, area.width.saturating_sub(2) as usize);
+        f.render_widget(Paragraph::new(Line::styled(format!(" {text}"), style)), area);
+        return;
+    }
+
+    let keymap = &app.keymap;
+    let mut hints: Vec<(String, &str)> = Vec::new();
+
+    if app.searching().is_some() {
+        // The search prompt has fixed keys, since it takes typed text
+        hints.extend([
+            ("\u{2191}/\u{2193}".to_string(), "select"),
+            ("enter".to_string(), "jump"),
+            ("esc".to_string(), "cancel"),
+        ]);
+    } else if app.showing_help().is_some() {
+        hints.extend(pair_hint(keymap, Action::Down, Action::Up, "scroll"));
+        hints.extend(key_hint(keymap, Action::Back, "close"));
+    } else if app.showing_contact_card() {
+        hints.extend(key_hint(keymap, Action::Back, "close"));
+    } else if app.picking_tag().is_some() || app.picking_theme().is_some() {
+        hints.extend(pair_hint(keymap, Action::Down, Action::Up, "select"));
+        hints.extend(key_hint(keymap, Action::Open, "apply"));
+        hints.extend(key_hint(keymap, Action::Back, "cancel"));
     } else {
-        "scroll"
-    };
+        let jk_action = if app.has_selection() {
+            "select"
+        } else {
+            "scroll"
+        };
+        hints.extend(pair_hint(keymap, Action::PrevTab, Action::NextTab, "navigate"));
+        hints.extend(pair_hint(keymap, Action::Down, Action::Up, jk_action));
+        if app.reading().is_some() {
+            hints.extend(key_hint(keymap, Action::Back, "back"));
+        } else if !app.posts().is_empty() {
+            hints.extend(key_hint(keymap, Action::Open, "open"));
+        } else if !app.categories().is_empty() {
+            hints.extend(key_hint(keymap, Action::Filter, "filter"));
+        }
+        if app.copyable().is_some() {
+            hints.extend(key_hint(keymap, Action::Copy, "copy"));
+        }
+        if !app.entries().is_empty() {
+            hints.extend(key_hint(keymap, Action::ContactCard, "qr"));
+        }
+        hints.extend(key_hint(keymap, Action::Search, "search"));
+        hints.extend(key_hint(keymap, Action::Command, "command"));
+        hints.extend(key_hint(keymap, Action::Help, "help"));
+        hints.extend(key_hint(keymap, Action::Quit, "quit"));
+    }
 
-    let spans = vec![
-        Span::styled(" h/l ", theme::KEY_HINT),
-        Span::styled("navigate", theme::KEY_ACTION),
-        Span::styled("  j/k ", theme::KEY_HINT),
-        Span::styled(jk_action, theme::KEY_ACTION),
-        Span::styled("  tab ", theme::KEY_HINT),
-        Span::styled("next", theme::KEY_ACTION),
-        Span::styled("  q ", theme::KEY_HINT),
-        Span::styled("quit", theme::KEY_ACTION),
-    ];
+    let mut spans = Vec::new();
+    for (i, (key, action)) in hints.into_iter().enumerate() {
+        let key = if i == 0 {
+            format!(" {key} ")
+        } else {
+            format!("  {key} ")
+        };
+        spans.push(Span::styled(key, theme.key_hint));
+        spans.push(Span::styled(action, theme.key_action));
+    }
 
     let help = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
     f.render_widget(help, area);
 }
 
+/// Render the command palette prompt in place of the footer, followed by
+/// the completions it could not choose between.
+fn render_command_prompt(theme: &Theme, prompt: &CommandState, f: &mut Frame, area: Rect) {
+    let mut spans = vec![
+        Span::styled(" :", theme.command_prompt),
+        Span::styled(prompt.input.as_str(), theme.text),
+        Span::styled("\u{2588}", theme.text_muted),
+    ];
+    if !prompt.candidates.is_empty() {
+        spans.push(Span::styled(
+            format!("   {}", prompt.candidates.join("  ")),
+            theme.command_candidate,
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Footer hint for the key bound to `action`, if any.
+fn key_hint<'a>(keymap: &Keymap, action: Action, label: &'a str) -> Option<(String, &'a str)> {
+    Some((keymap.hint(action)?, label))
+}
+
+/// Footer hint for a pair of opposite actions, e.g. "h/l navigate".
+fn pair_hint<'a>(
+    keymap: &Keymap,
+    first: Action,
+    second: Action,
+    label: &'a str,
+) -> Option<(String, &'a str)> {
+    match (keymap.hint(first), keymap.hint(second)) {
+        (Some(a), Some(b)) => Some((format!("{a}/{b}"), label)),
+        (Some(key), None) | (None, Some(key)) => Some((key, label)),
+        (None, None) => None,
+    }
+}
+
 // ── Scroll indicator ───────────────────────────────────────────
 
 fn render_scroll_indicator(
+    theme: &Theme,
     f: &mut Frame,
     area: Rect,
     offset: usize,
@@ -466,11 +1492,7 @@ fn render_scroll_indicator(
     }
 
     // Show a small position indicator at the top-right of the content area
-    let pct = if max_scroll > 0 {
-        (offset * 100) / max_scroll
-    } else {
-        0
-    };
+    let pct = (offset * 100).checked_div(max_scroll).unwrap_or(0);
 
     let indicator = if offset == 0 {
         "\u{2191} top".to_string()
@@ -487,6 +1509,52 @@ fn render_scroll_indicator(
         height: 1,
     };
 
-    let text = Paragraph::new(Span::styled(indicator, theme::SCROLL_INDICATOR));
+    let text = Paragraph::new(Span::styled(indicator, theme.scroll_indicator));
     f.render_widget(text, indicator_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::keymap::Keymap;
+    use crate::portfolio::Portfolio;
+    use crate::theme;
+
+    #[test]
+    fn long_key_value_entries_take_one_row_each() {
+        let raw = r#"
+            [[tabs]]
+            kind = "key_value"
+            title = "Contact"
+            entries = [
+                { label = "Web", value = "https://example.com/a/path/far/too/long/for/the/row" },
+                { label = "Email", value = "me@a.dev" },
+            ]
+            "#;
+        let portfolio = Arc::new(Portfolio::parse(raw).unwrap());
+        let mut app = App::new(
+            portfolio,
+            Arc::new(Keymap::default()),
+            theme::BUNDLED.into(),
+        );
+        app.skip_intro();
+        app.size = (40, 30);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+        let mut hits = HitMap::default();
+        terminal.draw(|f| hits = render(&app, f)).unwrap();
+
+        let rows: Vec<u16> = hits.items.iter().map(|(row, _)| row.y).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], rows[0] + 1);
+        let buffer = terminal.backend().buffer();
+        let line = |y: u16| (0..40).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(line(rows[0]).contains('\u{2026}'), "{}", line(rows[0]));
+        assert!(line(rows[1]).contains("Email"), "{}", line(rows[1]));
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::portfolio::{Portfolio, Tab};
+
+// ── Layout limits ──────────────────────────────────────────────
+//
+// Content must fit the smallest terminal we design for. These mirror
+// the layout in `ui.rs`: the content block has a 1-column border and
+// 2 columns of padding on each side, and the project list takes 40%
+// of it minus its own border, padding and the selection arrow.
+
+const MIN_COLUMNS: usize = 80;
+const CONTENT_WIDTH: usize = MIN_COLUMNS - 2 - 4;
+const PROJECT_LIST_WIDTH: usize = CONTENT_WIDTH * 40 / 100 - 1 - 2 - 3;
+
+/// A single problem found in a content file.
+pub struct Problem {
+    /// Line in the source file, when it can be located.
+    pub line: Option<usize>,
+    /// Path to the offending value, e.g. `tabs[1].categories[0].projects[2].url`.
+    pub location: String,
+    pub message: String,
+}
+
+/// Validate the content file at `path`, printing every problem found.
+/// Returns the process exit code: 0 if the file is valid, 1 otherwise.
+pub fn run(path: &Path) -> i32 {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            eprintln!("{}: failed to read: {e}", path.display());
+            return 1;
+        }
+    };
+
+    let mut portfolio = match Portfolio::parse(&raw) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return 1;
+        }
+    };
+    if let Err(e) = portfolio.load_posts(path.parent().unwrap_or(Path::new("."))) {
+        eprintln!("{}: {e:#}", path.display());
+        return 1;
+    }
+
+    let problems = check(&portfolio, &raw);
+    if problems.is_empty() {
+        println!("{}: ok", path.display());
+        return 0;
+    }
+
+    for p in &problems {
+        match p.line {
+            Some(line) => eprintln!("{}:{line}: {}: {}", path.display(), p.location, p.message),
+            None => eprintln!("{}: {}: {}", path.display(), p.location, p.message),
+        }
+    }
+    eprintln!(
+        "error: {} problem{} found",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    );
+    1
+}
+
+/// Check a parsed portfolio for problems. `raw` is the TOML source,
+/// used only to point at the line each problem comes from.
+pub fn check(portfolio: &Portfolio, raw: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut locator = Locator::new(raw);
+    let mut report = |line: Option<usize>, location: String, message: String| {
+        problems.push(Problem {
+            line,
+            location,
+            message,
+        });
+    };
+
+    // ── Banner ─────────────────────────────────────────────────
+    let banner_width = portfolio.banner.first().map(|l| l.width()).unwrap_or(0);
+    for (i, line) in portfolio.banner.iter().enumerate() {
+        let width = line.width();
+        if width != banner_width {
+            report(
+                locator.line_of(line, 0),
+                format!("banner[{i}]"),
+                format!(
+                    "line is {width} columns wide but banner[0] is {banner_width}; \
+                     pad lines to the same width or the header will not center as a block"
+                ),
+            );
+        }
+        if width > MIN_COLUMNS {
+            report(
+                locator.line_of(line, 0),
+                format!("banner[{i}]"),
+                too_wide(width, MIN_COLUMNS),
+            );
+        }
+    }
+
+    // ── Tabs ───────────────────────────────────────────────────
+    if portfolio.tabs.is_empty() {
+        report(None, "tabs".to_string(), "no tabs defined".to_string());
+    }
+
+    let mut titles: HashMap<String, usize> = HashMap::new();
+    let mut project_names: HashMap<String, String> = HashMap::new();
+    for (t, tab) in portfolio.tabs.iter().enumerate() {
+        let title_line = locator.next_line(tab.title());
+        if tab.title().trim().is_empty() {
+            report(
+                title_line,
+                format!("tabs[{t}].title"),
+                "empty tab title".to_string(),
+            );
+        } else if let Some(first) = titles.insert(tab.title().to_lowercase(), t) {
+            report(
+                title_line,
+                format!("tabs[{t}].title"),
+                format!(
+                    "duplicate tab title {:?} (first used at tabs[{first}])",
+                    tab.title()
+                ),
+            );
+        }
+
+        match tab {
+            Tab::Text { body, .. } => {
+                // Prose is reflowed to the viewport, but code blocks are not
+                for (n, line) in code_block_lines(body) {
+                    // Rendered with a 2-column indent
+                    let width = 2 + line.width();
+                    if width > CONTENT_WIDTH {
+                        report(
+                            locator.line_of_text(line),
+                            format!("tabs[{t}].body (code block line {n})"),
+                            too_wide(width, CONTENT_WIDTH),
+                        );
+                    }
+                }
+            }
+            Tab::Projects { categories, .. } => {
+                for (c, cat) in categories.iter().enumerate() {
+                    if cat.projects.is_empty() {
+                        report(
+                            locator.line_of(&cat.name, 0),
+                            format!("tabs[{t}].categories[{c}]"),
+                            format!("category {:?} has no projects", cat.name),
+                        );
+                    }
+
+                    for (p, project) in cat.projects.iter().enumerate() {
+                        let location = format!("tabs[{t}].categories[{c}].projects[{p}]");
+                        // Names and URLs legitimately repeat, so walk occurrences in
+                        // order to point each project at its own line.
+                        let name_line = locator.next_line(&project.name);
+                        let url_line = locator.next_line(&project.url);
+
+                        if let Some(first) = project_names.get(&project.name.to_lowercase()) {
+                            report(
+                                name_line,
+                                format!("{location}.name"),
+                                format!(
+                                    "duplicate project name {:?} (first used at {first})",
+                                    project.name
+                                ),
+                            );
+                        } else {
+                            project_names.insert(project.name.to_lowercase(), location.clone());
+                        }
+
+                        let width = project.name.width();
+                        if width > PROJECT_LIST_WIDTH {
+                            report(
+                                name_line,
+                                format!("{location}.name"),
+                                too_wide(width, PROJECT_LIST_WIDTH),
+                            );
+                        }
+
+                        if let Err(reason) = check_url(&project.url) {
+                            report(
+                                url_line,
+                                format!("{location}.url"),
+                                format!("malformed URL {:?}: {reason}", project.url),
+                            );
+                        }
+                    }
+                }
+            }
+            Tab::Timeline { entries, .. } => {
+                if entries.is_empty() {
+                    report(
+                        title_line,
+                        format!("tabs[{t}].entries"),
+                        "timeline has no entries".to_string(),
+                    );
+                }
+                for (i, role) in entries.iter().enumerate() {
+                    let location = format!("tabs[{t}].entries[{i}]");
+                    if role.start.trim().is_empty() {
+                        report(
+                            locator.line_of(&role.title, 0),
+                            format!("{location}.start"),
+                            "missing start date".to_string(),
+                        );
+                    }
+                    // The dot takes 2 columns where projects have a 3-column arrow
+                    let width = role.title.width();
+                    if width > PROJECT_LIST_WIDTH + 1 {
+                        report(
+                            locator.line_of(&role.title, 0),
+                            format!("{location}.title"),
+                            too_wide(width, PROJECT_LIST_WIDTH + 1),
+                        );
+                    }
+                }
+            }
+            Tab::Posts { posts, .. } => {
+                for post in posts {
+                    for (n, line) in code_block_lines(&post.body) {
+                        let width = 2 + line.width();
+                        if width > CONTENT_WIDTH {
+                            report(
+                                None,
+                                format!("{} (code block line {n})", post.path.display()),
+                                too_wide(width, CONTENT_WIDTH),
+                            );
+                        }
+                    }
+                }
+            }
+            Tab::Skills { groups, .. } => {
+                for (i, group) in groups.iter().enumerate() {
+                    let width = group.items.join("  \u{00b7}  ").width();
+                    if width > CONTENT_WIDTH {
+                        report(
+                            locator.line_of(&group.name, 0),
+                            format!("tabs[{t}].groups[{i}].items"),
+                            too_wide(width, CONTENT_WIDTH),
+                        );
+                    }
+                }
+            }
+            Tab::KeyValue {
+                intro,
+                entries,
+                outro,
+                ..
+            } => {
+                for (i, entry) in entries.iter().enumerate() {
+                    // Rendered as a 2-space indent plus a 10-column label
+                    let width = 2 + entry.label.width().max(10) + entry.value.width();
+                    if width > CONTENT_WIDTH {
+                        report(
+                            locator.line_of(&entry.value, 0),
+                            format!("tabs[{t}].entries[{i}]"),
+                            too_wide(width, CONTENT_WIDTH),
+                        );
+                    }
+                }
+                for (field, text) in [("intro", intro), ("outro", outro)] {
+                    let width = text.width();
+                    if width > CONTENT_WIDTH {
+                        report(
+                            locator.line_of(text, 0),
+                            format!("tabs[{t}].{field}"),
+                            too_wide(width, CONTENT_WIDTH),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+fn too_wide(width: usize, max: usize) -> String {
+    format!("line is {width} columns wide, more than the {max} available at {MIN_COLUMNS} columns")
+}
+
+/// Lines inside fenced code blocks of a Markdown text, with their
+/// 1-based line number within the text. Code blocks are rendered
+/// verbatim (plus a 2-column indent), so these cannot be reflowed.
+fn code_block_lines(markdown: &str) -> Vec<(usize, &str)> {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for (i, line) in markdown.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if in_fence {
+            lines.push((i + 1, line));
+        }
+    }
+    lines
+}
+
+/// Check that a project URL is an absolute http(s) URL with a host.
+/// An empty URL is fine: the project simply has no link.
+fn check_url(url: &str) -> Result<(), &'static str> {
+    if url.is_empty() {
+        return Ok(());
+    }
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Err("expected an http:// or https:// URL");
+    };
+    if url.chars().any(char::is_whitespace) {
+        return Err("URLs cannot contain whitespace");
+    }
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit_once(':').map_or(host, |(h, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) {
+            h
+        } else {
+            host
+        }
+    });
+    if host.is_empty() {
+        return Err("missing host");
+    }
+    if !host
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+        || host.starts_with(['.', '-'])
+        || host.ends_with(['.', '-'])
+    {
+        return Err("invalid host");
+    }
+    if !host.contains('.') && host != "localhost" {
+        return Err("host has no domain");
+    }
+    Ok(())
+}
+
+/// Maps values back to the line of the TOML source they appear on.
+///
+/// Values are looked up as basic (double-quoted) strings, so values
+/// written as literal strings or with escapes are not found.
+struct Locator<'a> {
+    raw: &'a str,
+    /// How many occurrences of each value `next_line` has handed out.
+    seen: HashMap<String, usize>,
+}
+
+impl<'a> Locator<'a> {
+    fn new(raw: &'a str) -> Self {
+        Self {
+            raw,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Line number (1-based) of the first line containing `text` verbatim,
+    /// for values written in multi-line strings.
+    fn line_of_text(&self, text: &str) -> Option<usize> {
+        let offset = self.raw.find(text)?;
+        Some(self.raw[..offset].matches('\n').count() + 1)
+    }
+
+    /// Line number of the next not-yet-visited occurrence of `value`.
+    fn next_line(&mut self, value: &str) -> Option<usize> {
+        let nth = self.seen.entry(value.to_string()).or_default();
+        *nth += 1;
+        let nth = *nth - 1;
+        self.line_of(value, nth)
+    }
+
+    /// Line number (1-based) of the `nth` occurrence of `value`.
+    fn line_of(&self, value: &str, nth: usize) -> Option<usize> {
+        let quoted = format!("{value:?}");
+        let (offset, _) = self.raw.match_indices(&quoted).nth(nth)?;
+        Some(self.raw[..offset].matches('\n').count() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(raw: &str) -> Vec<(Option<usize>, String)> {
+        let portfolio = Portfolio::parse(raw).unwrap();
+        check(&portfolio, raw)
+            .into_iter()
+            .map(|p| (p.line, p.location))
+            .collect()
+    }
+
+    fn problem(line: usize, location: &str) -> (Option<usize>, String) {
+        (Some(line), location.to_string())
+    }
+
+    #[test]
+    fn builtin_content_is_valid() {
+        assert!(check(&Portfolio::builtin(), "").is_empty());
+    }
+
+    #[test]
+    fn banner_lines_must_share_a_width() {
+        let raw = "banner = [\n  \"abcd\",\n  \"abc\",\n]\n";
+        assert_eq!(problems(raw), [problem(3, "banner[1]")]);
+
+        let wide = format!("banner = [\"{}\"]\n", "x".repeat(MIN_COLUMNS + 1));
+        assert_eq!(problems(&wide), [problem(1, "banner[0]")]);
+    }
+
+    #[test]
+    fn tabs_need_distinct_titles() {
+        let raw = r#"
+[[tabs]]
+kind = "text"
+title = "About"
+body = ""
+
+[[tabs]]
+kind = "text"
+title = "about"
+body = ""
+"#;
+        assert_eq!(problems(raw), [problem(9, "tabs[1].title")]);
+        assert_eq!(problems("tabs = []"), [(None, "tabs".to_string())]);
+    }
+
+    #[test]
+    fn duplicate_project_names_point_at_the_second() {
+        let raw = r#"
+[[tabs]]
+kind = "projects"
+title = "Projects"
+
+[[tabs.categories]]
+name = "Apps"
+projects = [
+  { name = "miru", description = "", tech = "", url = "https://a.dev" },
+  { name = "Miru", description = "", tech = "", url = "https://a.dev" },
+]
+"#;
+        assert_eq!(
+            problems(raw),
+            [problem(10, "tabs[0].categories[0].projects[1].name")]
+        );
+    }
+
+    #[test]
+    fn overlong_code_block_lines_are_reported() {
+        let code = "x".repeat(CONTENT_WIDTH - 1);
+        let prose = "y".repeat(CONTENT_WIDTH * 2);
+        let raw = format!(
+            "[[tabs]]\nkind = \"text\"\ntitle = \"About\"\nbody = \"\"\"\n{prose}\n```\nok\n{code}\n```\n\"\"\"\n"
+        );
+        assert_eq!(
+            problems(&raw),
+            [problem(8, "tabs[0].body (code block line 4)")]
+        );
+    }
+
+    #[test]
+    fn overlong_key_value_lines_are_reported() {
+        let raw = format!(
+            "[[tabs]]\nkind = \"key_value\"\ntitle = \"Contact\"\nentries = []\noutro = \"{}\"\n",
+            "x".repeat(CONTENT_WIDTH + 1)
+        );
+        assert_eq!(problems(&raw), [problem(5, "tabs[0].outro")]);
+    }
+
+    #[test]
+    fn timeline_roles_need_a_start_date() {
+        let raw = r#"
+[[tabs]]
+kind = "timeline"
+title = "Work"
+
+[[tabs.entries]]
+title = "Engineer"
+client = "Acme"
+start = " "
+"#;
+        assert_eq!(problems(raw), [problem(7, "tabs[0].entries[0].start")]);
+
+        let empty = "[[tabs]]\nkind = \"timeline\"\ntitle = \"Work\"\nentries = []\n";
+        assert_eq!(problems(empty), [problem(3, "tabs[0].entries")]);
+    }
+
+    #[test]
+    fn urls_need_a_scheme_and_a_host() {
+        assert!(check_url("https://example.com/path?q=1").is_ok());
+        assert!(check_url("http://localhost:8080").is_ok());
+        assert!(check_url("").is_ok());
+        assert!(check_url(" ").is_err());
+        assert!(check_url("example.com").is_err());
+        assert!(check_url("ftp://example.com").is_err());
+        assert!(check_url("https://").is_err());
+        assert!(check_url("https://exa mple.com").is_err());
+        assert!(check_url("https://example").is_err());
+        assert!(check_url("https://-example.com").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::hyperlink::Link;
+use crate::portfolio::{Portfolio, Tab};
+
+// ── vCard ──────────────────────────────────────────────────────
+//
+// The portfolio owner's contact card as a vCard 4.0 (RFC 6350), built
+// from the portfolio name and the entries of the first key/value tab.
+// `ssh host vcard` prints it, and the Contact tab shows it as a QR code
+// for phones to scan. Email addresses become EMAIL properties, phone
+// numbers TEL, links URL (labelled the way Apple Contacts reads them),
+// and anything else goes into the NOTE.
+
+/// Longest line, in bytes, before it is folded onto the next.
+const FOLD_AT: usize = 75;
+
+/// The portfolio's contact card.
+pub fn from_portfolio(portfolio: &Portfolio) -> String {
+    let mut card = Card::default();
+    card.push("BEGIN:VCARD");
+    card.push("VERSION:4.0");
+    card.push(&format!("FN:{}", escape(&portfolio.name)));
+
+    // The last word is taken as the family name
+    let (given, family) = match portfolio.name.trim().rsplit_once(' ') {
+        Some((given, family)) => (given, family),
+        None => (portfolio.name.trim(), ""),
+    };
+    card.push(&format!("N:{};{};;;", escape(family), escape(given)));
+
+    let entries = portfolio.tabs.iter().find_map(|t| match t {
+        Tab::KeyValue { entries, .. } => Some(entries),
+        _ => None,
+    });
+    let mut notes = Vec::new();
+    let mut links = 0;
+    for entry in entries.into_iter().flatten() {
+        let value = entry.value.trim();
+        if is_phone(value) {
+            let number: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+            card.push(&format!("TEL;VALUE=uri:tel:{number}"));
+            continue;
+        }
+        match Link::detect(value) {
+            Some(link) if link.target.starts_with("mailto:") => {
+                let address = &link.target["mailto:".len()..];
+                card.push(&format!("EMAIL:{}", escape(address)));
+            }
+            Some(link) => {
+                links += 1;
+                card.push(&format!("item{links}.URL:{}", link.target));
+                card.push(&format!("item{links}.X-ABLABEL:{}", escape(&entry.label)));
+            }
+            None => notes.push(format!("{}: {value}", entry.label)),
+        }
+    }
+    if !notes.is_empty() {
+        card.push(&format!("NOTE:{}", escape(&notes.join("\n"))));
+    }
+
+    card.push("END:VCARD");
+    card.0
+}
+
+/// vCard content lines, folded and CRLF-terminated.
+#[derive(Default)]
+struct Card(String);
+
+impl Card {
+    fn push(&mut self, line: &str) {
+        let mut width = 0;
+        for c in line.chars() {
+            // Continuation lines start with a space, which counts
+            if width + c.len_utf8() > FOLD_AT {
+                self.0.push_str("\r\n ");
+                width = 1;
+            }
+            self.0.push(c);
+            width += c.len_utf8();
+        }
+        self.0.push_str("\r\n");
+    }
+}
+
+/// Escape a text value.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ',' | ';' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Whether `value` looks like a phone number, e.g. `+33 6 12 34 56 78`.
+fn is_phone(value: &str) -> bool {
+    let digits = value.chars().filter(char::is_ascii_digit).count();
+    digits >= 6
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '.' | '(' | ')'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_protects_separators_and_newlines() {
+        assert_eq!(escape(r"a,b;c\d"), r"a\,b\;c\\d");
+        assert_eq!(escape("one\r\ntwo"), "one\\ntwo");
+    }
+
+    #[test]
+    fn phone_numbers_need_six_digits_and_nothing_else() {
+        assert!(is_phone("+33 6 12 34 56 78"));
+        assert!(is_phone("(555) 123-4567"));
+        assert!(!is_phone("12345"));
+        assert!(!is_phone("2024 edition"));
+        assert!(!is_phone("hello@example.com"));
+    }
+
+    #[test]
+    fn long_lines_fold_at_75_octets() {
+        let mut card = Card::default();
+        card.push(&format!("NOTE:{}", "x".repeat(200)));
+        let lines: Vec<&str> = card.0.split_terminator("\r\n").collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= FOLD_AT), "{lines:?}");
+        assert!(lines[1..].iter().all(|l| l.starts_with(' ')));
+        let unfolded: String = lines
+            .iter()
+            .map(|l| l.strip_prefix(' ').unwrap_or(l))
+            .collect();
+        assert_eq!(unfolded, format!("NOTE:{}", "x".repeat(200)));
+    }
+
+    #[test]
+    fn folding_counts_octets_not_characters() {
+        let mut card = Card::default();
+        card.push(&format!("FN:{}", "é".repeat(60)));
+        let lines: Vec<&str> = card.0.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= FOLD_AT), "{lines:?}");
+    }
+}